@@ -38,4 +38,8 @@ pub enum Subcommand {
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Replay the draw of a Kleroterion tribe off-chain.
+	#[structopt(name = "verify-draw")]
+	VerifyDraw(crate::verify_draw::VerifyDrawCmd),
 }
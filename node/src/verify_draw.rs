@@ -0,0 +1,204 @@
+//! Off-chain replay of the draw of a Kleroterion tribe.
+
+use kleroterion_sortition::{
+	AlgorithmVersion, AttributeKey, AttributeValue, CandidateWeight, Quota, Selections,
+};
+use node_template_runtime::AccountId;
+use sp_core::{crypto::Ss58Codec, H256};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use structopt::StructOpt;
+
+/// Replays the draw of a tribe from the seed recorded on chain and prints its ranking,
+/// or checks it against the ranking stored on chain.
+#[derive(Debug, StructOpt)]
+pub struct VerifyDrawCmd {
+	/// Seed of the draw, as found in its draw record (hex).
+	#[structopt(long)]
+	pub seed: String,
+
+	/// Index of the tribe in the Jury Call.
+	#[structopt(long, default_value = "0")]
+	pub tribe: u16,
+
+	/// Number of tribes of the Jury Call, required by an Athenian draw
+	/// since a tribe without candidates still takes a column.
+	#[structopt(long)]
+	pub tribes: Option<u16>,
+
+	/// Draw mode of the Jury Call.
+	#[structopt(
+		long,
+		default_value = "uniform",
+		possible_values = &["uniform", "weighted", "stratified", "athenian"]
+	)]
+	pub mode: String,
+
+	/// Version of the draw algorithm of the Jury Call, the latest one by default.
+	#[structopt(long)]
+	pub algorithm: Option<AlgorithmVersion>,
+
+	/// Number of seats of the tribe.
+	#[structopt(long)]
+	pub seats: Selections,
+
+	/// Quota of a stratified draw, as `KEY:VALUE:MIN:MAX`. Can be repeated.
+	#[structopt(long = "quota")]
+	pub quotas: Vec<String>,
+
+	/// Ranking stored on chain, as SS58 addresses. The command fails if the replay differs.
+	#[structopt(long = "expect")]
+	pub expected: Vec<String>,
+
+	/// Candidates of the tribe, as SS58 addresses followed by `:WEIGHT` in a weighted draw
+	/// or by `:KEY=VALUE,KEY=VALUE` in a stratified draw. An Athenian draw ranks every tribe
+	/// at once, so it takes the candidates of all the tribes followed by `:TRIBE` and needs `--tribes`.
+	pub candidates: Vec<String>,
+}
+
+impl VerifyDrawCmd {
+	/// Run the command
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let (ranking, jurors) = self.replay()?;
+
+		if !self.expected.is_empty() {
+			let expected = self.expected.iter().map(|address| parse_account(address)).collect::<Result<Vec<_>, _>>()?;
+			if expected != ranking {
+				return Err(sc_cli::Error::Input("The replayed draw differs from the expected ranking".into()))
+			}
+		}
+
+		for (rank, who) in ranking.iter().enumerate() {
+			let role = if rank < jurors { "juror" } else { "alternate" };
+			println!("{} {} {}", rank + 1, who.to_ss58check(), role);
+		}
+		Ok(())
+	}
+
+	/// Ranks the candidates the way the pallet does, from the same inputs,
+	/// and gives the number of jurors at the top of the ranking
+	fn replay(&self) -> sc_cli::Result<(Vec<AccountId>, usize)> {
+		let bytes = sp_core::bytes::from_hex(&self.seed)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid seed: {}", e)))?;
+		if bytes.len() != H256::len_bytes() {
+			return Err(sc_cli::Error::Input("The seed must be 32 bytes long".into()))
+		}
+		let draw_seed = H256::from_slice(&bytes);
+		let seed = BlakeTwo256::hash_of(&(draw_seed, self.tribe));
+		let algorithm = self.algorithm.unwrap_or(kleroterion_sortition::LATEST_VERSION);
+		if !kleroterion_sortition::is_supported(algorithm) {
+			return Err(sc_cli::Error::Input(format!("Unsupported draw algorithm {}", algorithm)))
+		}
+
+		// The pallet draws among the candidates sorted by account
+		let mut candidates = self
+			.candidates
+			.iter()
+			.map(|candidate| {
+				let (address, details) = match candidate.split_once(':') {
+					Some((address, details)) => (address, Some(details)),
+					None => (candidate.as_str(), None),
+				};
+				Ok((parse_account(address)?, details))
+			})
+			.collect::<sc_cli::Result<Vec<_>>>()?;
+		candidates.sort_by(|a, b| a.0.cmp(&b.0));
+
+		let seats = self.seats as usize;
+		let ranking = match self.mode.as_str() {
+			"athenian" => {
+				let tribes = self
+					.tribes
+					.ok_or_else(|| sc_cli::Error::Input("An Athenian draw needs the number of tribes".into()))?
+					as usize;
+				if tribes <= self.tribe as usize {
+					return Err(sc_cli::Error::Input(format!("No tribe {} among {} tribes", self.tribe, tribes)))
+				}
+				let mut columns: Vec<Vec<AccountId>> = vec![Vec::new(); tribes];
+				for (who, tribe) in candidates {
+					let tribe: usize = tribe
+						.unwrap_or("")
+						.parse()
+						.map_err(|_| sc_cli::Error::Input(format!("Invalid tribe for {}", who)))?;
+					columns
+						.get_mut(tribe)
+						.ok_or_else(|| sc_cli::Error::Input(format!("No tribe {} among {} tribes", tribe, tribes)))?
+						.push(who);
+				}
+				let (mut rankings, dice) =
+					kleroterion_sortition::athenian::<BlakeTwo256, _>(algorithm, &draw_seed, &columns, seats)
+						.unwrap_or_default();
+				let dice: String = dice.iter().map(|die| if *die { 'W' } else { 'B' }).collect();
+				println!("dice {}", dice);
+				let white = dice.chars().filter(|die| *die == 'W').count();
+				return Ok((rankings.swap_remove(self.tribe as usize), white))
+			},
+			"weighted" => {
+				let candidates = candidates
+					.into_iter()
+					.map(|(who, weight)| {
+						let weight: CandidateWeight = weight
+							.unwrap_or("1")
+							.parse()
+							.map_err(|_| sc_cli::Error::Input(format!("Invalid weight for {}", who)))?;
+						Ok((who, weight))
+					})
+					.collect::<sc_cli::Result<Vec<_>>>()?;
+				kleroterion_sortition::weighted_shuffle::<BlakeTwo256, _>(algorithm, &seed, &candidates)
+					.unwrap_or_default()
+			},
+			"stratified" => {
+				let quotas = self.quotas.iter().map(|quota| parse_quota(quota)).collect::<sc_cli::Result<Vec<_>>>()?;
+				let candidates = candidates
+					.into_iter()
+					.map(|(who, attributes)| Ok((who, parse_attributes(attributes.unwrap_or(""))?)))
+					.collect::<sc_cli::Result<Vec<_>>>()?;
+				kleroterion_sortition::stratified_shuffle::<BlakeTwo256, _>(
+					algorithm,
+					&seed,
+					&candidates,
+					&quotas,
+					seats,
+				)
+				.ok_or_else(|| sc_cli::Error::Input("The quotas cannot be met by these candidates".into()))?
+			},
+			_ => {
+				let candidates: Vec<AccountId> = candidates.into_iter().map(|(who, _)| who).collect();
+				kleroterion_sortition::shuffle::<BlakeTwo256, _>(algorithm, &seed, &candidates)
+					.unwrap_or_default()
+			},
+		};
+		let jurors = ranking.len().min(seats);
+		Ok((ranking, jurors))
+	}
+}
+
+fn parse_account(address: &str) -> sc_cli::Result<AccountId> {
+	AccountId::from_ss58check(address)
+		.map_err(|e| sc_cli::Error::Input(format!("Invalid address {}: {:?}", address, e)))
+}
+
+/// Parses a quota written `KEY:VALUE:MIN:MAX`
+fn parse_quota(quota: &str) -> sc_cli::Result<Quota> {
+	let invalid = || sc_cli::Error::Input(format!("Invalid quota {}", quota));
+	let fields = quota
+		.split(':')
+		.map(|field| field.parse::<u16>().map_err(|_| invalid()))
+		.collect::<sc_cli::Result<Vec<_>>>()?;
+	match fields[..] {
+		[key, value, min, max] => Ok(Quota { key, value, min, max }),
+		_ => Err(invalid()),
+	}
+}
+
+/// Parses attributes written `KEY=VALUE,KEY=VALUE`
+fn parse_attributes(attributes: &str) -> sc_cli::Result<Vec<(AttributeKey, AttributeValue)>> {
+	attributes
+		.split(',')
+		.filter(|attribute| !attribute.is_empty())
+		.map(|attribute| {
+			let invalid = || sc_cli::Error::Input(format!("Invalid attribute {}", attribute));
+			let (key, value) = attribute.split_once('=').ok_or_else(invalid)?;
+			Ok((key.parse().map_err(|_| invalid())?, value.parse().map_err(|_| invalid())?))
+		})
+		.collect()
+}
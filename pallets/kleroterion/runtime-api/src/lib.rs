@@ -0,0 +1,17 @@
+//! Runtime API to replay the draws of pallet-kleroterion
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_kleroterion::JuryCallID;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Lets anyone check that the jurors of a Jury Call match its draw record.
+	pub trait KleroterionApi<AccountId> where AccountId: Codec {
+		/// Recomputes the ranking of every tribe of a Jury Call from its draw record.
+		/// Returns None when the draw cannot be replayed.
+		fn replay_draw(jury_call_id: JuryCallID) -> Option<Vec<Vec<AccountId>>>;
+		/// Returns true if replaying the draw of a Jury Call gives the stored rankings.
+		fn verify_draw(jury_call_id: JuryCallID) -> bool;
+	}
+}
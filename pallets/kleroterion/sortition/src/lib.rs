@@ -0,0 +1,98 @@
+//! Seeded sortition algorithms of the Kleroterion.
+//!
+//! Every function is deterministic: given the same seed, hasher and candidates it returns the same
+//! ranking, so that the pallet, the node CLI and anyone else can replay a draw.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod tests;
+
+pub mod v1;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_runtime::{traits::Hash, RuntimeDebug};
+use sp_std::vec::Vec;
+
+pub type Selections = u16;
+pub type CandidateWeight = u32;
+pub type AttributeKey = u16;
+pub type AttributeValue = u16;
+pub type AlgorithmVersion = u16;
+
+/// Version of the algorithms used by new draws. Every previous version stays callable,
+/// so that a runtime upgrade never changes the outcome of a draw.
+pub const LATEST_VERSION: AlgorithmVersion = 1;
+
+/// Minimum and maximum number of jurors of a tribe who share an attribute value,
+/// e.g. at least 4 and at most 6 jurors whose attribute "region" is "north".
+#[derive(Clone, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+pub struct Quota {
+	pub key: AttributeKey,
+	pub value: AttributeValue,
+	pub min: Selections,
+	pub max: Selections,
+}
+
+impl Quota {
+	/// Returns true if the attributes hold the value this quota counts
+	pub fn counts(&self, attributes: &[(AttributeKey, AttributeValue)]) -> bool {
+		attributes.iter().any(|(key, value)| *key == self.key && *value == self.value)
+	}
+}
+
+/// Returns true if this crate implements the version of the algorithms
+pub fn is_supported(version: AlgorithmVersion) -> bool {
+	(1..=LATEST_VERSION).contains(&version)
+}
+
+/// Randomly orders the candidates with the given version of the algorithms.
+/// Returns None if the version is not supported.
+pub fn shuffle<H: Hash, C: Clone>(version: AlgorithmVersion, seed: &H::Output, candidates: &[C]) -> Option<Vec<C>> {
+	match version {
+		1 => Some(v1::shuffle::<H, C>(seed, candidates)),
+		_ => None,
+	}
+}
+
+/// Randomly orders the candidates in proportion to their weights with the given version of the algorithms.
+/// Returns None if the version is not supported.
+pub fn weighted_shuffle<H: Hash, C: Clone>(
+	version: AlgorithmVersion,
+	seed: &H::Output,
+	candidates: &[(C, CandidateWeight)],
+) -> Option<Vec<C>> {
+	match version {
+		1 => Some(v1::weighted_shuffle::<H, C>(seed, candidates)),
+		_ => None,
+	}
+}
+
+/// Draws a panel meeting the quotas with the given version of the algorithms.
+/// Returns None if the version is not supported or the quotas cannot be met.
+pub fn stratified_shuffle<H: Hash, C: Clone>(
+	version: AlgorithmVersion,
+	seed: &H::Output,
+	candidates: &[(C, Vec<(AttributeKey, AttributeValue)>)],
+	quotas: &[Quota],
+	seats: usize,
+) -> Option<Vec<C>> {
+	match version {
+		1 => v1::stratified_shuffle::<H, C>(seed, candidates, quotas, seats),
+		_ => None,
+	}
+}
+
+/// Draws the jurors of every tribe with the Athenian allotment machine of the given version of the algorithms.
+/// Returns None if the version is not supported.
+pub fn athenian<H: Hash, C: Clone>(
+	version: AlgorithmVersion,
+	seed: &H::Output,
+	columns: &[Vec<C>],
+	seats: usize,
+) -> Option<(Vec<Vec<C>>, Vec<bool>)> {
+	match version {
+		1 => Some(v1::athenian::<H, C>(seed, columns, seats)),
+		_ => None,
+	}
+}
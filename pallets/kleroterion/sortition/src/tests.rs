@@ -0,0 +1,182 @@
+use crate::{
+	v1::{athenian, random_below, shuffle, stratified_shuffle, weighted_shuffle},
+	AttributeKey, AttributeValue, CandidateWeight, Quota,
+};
+use proptest::prelude::*;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+type Seed = <BlakeTwo256 as Hash>::Output;
+
+fn seed(n: u64) -> Seed {
+	BlakeTwo256::hash_of(&n)
+}
+
+fn sorted<C: Ord + Clone>(items: &[C]) -> Vec<C> {
+	let mut items = items.to_vec();
+	items.sort();
+	items
+}
+
+/// Returns true if every count is within `tolerance` times the expected count of its mean
+fn near_uniform(counts: &[u32], tolerance: f64) -> bool {
+	let mean = counts.iter().sum::<u32>() as f64 / counts.len() as f64;
+	counts.iter().all(|count| (*count as f64 - mean).abs() <= mean * tolerance)
+}
+
+proptest! {
+	#[test]
+	fn random_below_stays_below_bound(n in any::<u64>(), bound in 1u64..) {
+		let mut nonce = 0;
+		prop_assert!(random_below::<BlakeTwo256>(&seed(n), &mut nonce, bound) < bound);
+	}
+
+	#[test]
+	fn shuffle_is_a_permutation(n in any::<u64>(), len in 0usize..64) {
+		let candidates: Vec<usize> = (0..len).collect();
+		let ranking = shuffle::<BlakeTwo256, usize>(&seed(n), &candidates);
+		prop_assert_eq!(sorted(&ranking), candidates);
+	}
+
+	#[test]
+	fn shuffle_is_deterministic(n in any::<u64>(), len in 0usize..64) {
+		let candidates: Vec<usize> = (0..len).collect();
+		prop_assert_eq!(
+			shuffle::<BlakeTwo256, usize>(&seed(n), &candidates),
+			shuffle::<BlakeTwo256, usize>(&seed(n), &candidates)
+		);
+	}
+
+	#[test]
+	fn weighted_shuffle_is_a_permutation(n in any::<u64>(), weights in prop::collection::vec(0u32..100, 0..32)) {
+		let candidates: Vec<(usize, CandidateWeight)> = weights.into_iter().enumerate().collect();
+		let ranking = weighted_shuffle::<BlakeTwo256, usize>(&seed(n), &candidates);
+		prop_assert_eq!(sorted(&ranking), (0..candidates.len()).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn weighted_shuffle_ranks_zero_weights_last(n in any::<u64>(), weights in prop::collection::vec(0u32..3, 0..32)) {
+		let candidates: Vec<(usize, CandidateWeight)> = weights.iter().copied().enumerate().collect();
+		let ranking = weighted_shuffle::<BlakeTwo256, usize>(&seed(n), &candidates);
+		let weighted = weights.iter().filter(|weight| **weight > 0).count();
+		prop_assert!(ranking[..weighted].iter().all(|who| weights[*who] > 0));
+	}
+
+	#[test]
+	fn athenian_selects_whole_rows(
+		n in any::<u64>(),
+		lengths in prop::collection::vec(0usize..12, 1..6),
+		seats in 0usize..8,
+	) {
+		// Token t of column c is (c, t)
+		let columns: Vec<Vec<(usize, usize)>> = lengths
+			.iter()
+			.enumerate()
+			.map(|(column, len)| (0..*len).map(|token| (column, token)).collect())
+			.collect();
+		let (rankings, dice) = athenian::<BlakeTwo256, (usize, usize)>(&seed(n), &columns, seats);
+		let rows = lengths.iter().copied().min().unwrap();
+		let white = dice.iter().filter(|die| **die).count();
+		prop_assert_eq!(dice.len(), rows);
+		prop_assert_eq!(white, seats.min(rows));
+		for (column, ranking) in columns.iter().zip(rankings.iter()) {
+			prop_assert_eq!(sorted(ranking), column.clone());
+		}
+		// The jurors of every tribe sit in the same rows, which are those of the white dice
+		let slots: Vec<Vec<(usize, usize)>> = columns
+			.iter()
+			.enumerate()
+			.map(|(index, column)| shuffle::<BlakeTwo256, (usize, usize)>(&BlakeTwo256::hash_of(&(seed(n), index as u16)), column))
+			.collect();
+		for (slots, ranking) in slots.iter().zip(rankings.iter()) {
+			let selected: Vec<(usize, usize)> = dice.iter().zip(slots.iter()).filter(|(die, _)| **die).map(|(_, token)| *token).collect();
+			prop_assert_eq!(&ranking[..white], &selected[..]);
+		}
+	}
+
+	#[test]
+	fn stratified_shuffle_meets_quotas(
+		n in any::<u64>(),
+		values in prop::collection::vec(0u16..3, 1..32),
+		seats in 1usize..16,
+		min in 0u16..4,
+	) {
+		let candidates: Vec<(usize, Vec<(AttributeKey, AttributeValue)>)> = values
+			.iter()
+			.enumerate()
+			.map(|(who, value)| (who, vec![(0, *value)]))
+			.collect();
+		let quotas = vec![Quota { key: 0, value: 0, min, max: min + 2 }];
+		if let Some(ranking) = stratified_shuffle::<BlakeTwo256, usize>(&seed(n), &candidates, &quotas, seats) {
+			prop_assert_eq!(sorted(&ranking), (0..candidates.len()).collect::<Vec<_>>());
+			let panel = &ranking[..seats.min(candidates.len())];
+			let count = panel.iter().filter(|who| quotas[0].counts(&candidates[**who].1)).count();
+			prop_assert!(count >= min as usize && count <= (min + 2) as usize);
+		}
+	}
+}
+
+#[test]
+fn shuffle_is_uniform() {
+	// Every candidate must come first and last about as often as the others
+	let candidates: Vec<usize> = (0..8).collect();
+	let mut first = vec![0u32; candidates.len()];
+	let mut last = vec![0u32; candidates.len()];
+	for n in 0..8_000 {
+		let ranking = shuffle::<BlakeTwo256, usize>(&seed(n), &candidates);
+		first[ranking[0]] += 1;
+		last[ranking[candidates.len() - 1]] += 1;
+	}
+	assert!(near_uniform(&first, 0.15));
+	assert!(near_uniform(&last, 0.15));
+}
+
+#[test]
+fn random_below_is_uniform() {
+	let mut counts = vec![0u32; 7];
+	let mut nonce = 0;
+	for _ in 0..7_000 {
+		counts[random_below::<BlakeTwo256>(&seed(0), &mut nonce, 7) as usize] += 1;
+	}
+	assert!(near_uniform(&counts, 0.15));
+}
+
+#[test]
+fn weighted_shuffle_is_proportional_to_weights() {
+	// Candidate 1 weighs three times as much as candidate 0 and must come first three times as often
+	let candidates = vec![(0usize, 1), (1usize, 3)];
+	let mut first = vec![0u32; 2];
+	for n in 0..8_000 {
+		first[weighted_shuffle::<BlakeTwo256, usize>(&seed(n), &candidates)[0]] += 1;
+	}
+	assert!(near_uniform(&[first[0] * 3, first[1]], 0.1));
+}
+
+#[test]
+fn stratified_shuffle_fails_on_infeasible_quotas() {
+	let candidates = vec![(0usize, vec![(0, 1)]), (1usize, vec![(0, 1)])];
+	let quotas = vec![Quota { key: 0, value: 0, min: 1, max: 2 }];
+	assert_eq!(stratified_shuffle::<BlakeTwo256, usize>(&seed(0), &candidates, &quotas, 2), None);
+}
+
+#[test]
+fn athenian_skips_rows_with_an_empty_slot() {
+	let columns = vec![vec![1, 2, 3, 4], vec![5, 6]];
+	let (rankings, dice) = athenian::<BlakeTwo256, u32>(&seed(0), &columns, 5);
+	assert_eq!(dice, vec![true, true]);
+	assert_eq!(rankings[0].len(), 4);
+	assert_eq!(sorted(&rankings[1][..2]), vec![5, 6]);
+
+	let (rankings, dice) = athenian::<BlakeTwo256, u32>(&seed(0), &[vec![1, 2], vec![]], 1);
+	assert!(dice.is_empty());
+	assert_eq!(sorted(&rankings[0]), vec![1, 2]);
+}
+
+#[test]
+fn versions_dispatch_to_their_algorithms() {
+	let candidates: Vec<usize> = (0..16).collect();
+	assert!(crate::is_supported(crate::LATEST_VERSION));
+	assert_eq!(crate::shuffle::<BlakeTwo256, usize>(1, &seed(0), &candidates), Some(shuffle::<BlakeTwo256, usize>(&seed(0), &candidates)));
+	assert!(!crate::is_supported(0));
+	assert_eq!(crate::shuffle::<BlakeTwo256, usize>(0, &seed(0), &candidates), None);
+	assert_eq!(crate::weighted_shuffle::<BlakeTwo256, usize>(crate::LATEST_VERSION + 1, &seed(0), &[(0, 1)]), None);
+}
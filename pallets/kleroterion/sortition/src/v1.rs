@@ -0,0 +1,166 @@
+//! Version 1 of the draw algorithms. Never change it: recorded draws must keep replaying the same way.
+
+use crate::{AttributeKey, AttributeValue, CandidateWeight, Quota};
+use codec::Decode;
+use sp_runtime::traits::Hash;
+use sp_std::vec::Vec;
+
+/// Number of random orders tried before a stratified draw gives up.
+pub const STRATIFIED_ATTEMPTS: u32 = 8;
+
+/// Subject mixed into the seed of the dice tube of an Athenian draw.
+const DICE_SUBJECT: &[u8] = b"kleroterion/dice";
+
+/// Randomly orders the candidates with a Fisher-Yates shuffle seeded with `seed`.
+pub fn shuffle<H: Hash, C: Clone>(seed: &H::Output, candidates: &[C]) -> Vec<C> {
+	let mut pool = candidates.to_vec();
+	let mut nonce: u32 = 0;
+	for i in 0..pool.len() {
+		let j = i + random_below::<H>(seed, &mut nonce, (pool.len() - i) as u64) as usize;
+		pool.swap(i, j);
+	}
+	pool
+}
+
+/// Randomly orders the candidates by drawing them one at a time without replacement,
+/// each with a probability proportional to its weight among the remaining candidates.
+pub fn weighted_shuffle<H: Hash, C: Clone>(seed: &H::Output, candidates: &[(C, CandidateWeight)]) -> Vec<C> {
+	let mut pool = candidates.to_vec();
+	let mut total: u64 = pool.iter().map(|(_, weight)| *weight as u64).sum();
+	let mut ranking = Vec::with_capacity(pool.len());
+	let mut nonce: u32 = 0;
+	while !pool.is_empty() {
+		// Candidates without weight come last, in a uniform order
+		if total == 0 {
+			let rest: Vec<C> = pool.into_iter().map(|(who, _)| who).collect();
+			ranking.extend(shuffle::<H, C>(seed, &rest));
+			break;
+		}
+		let mut target = random_below::<H>(seed, &mut nonce, total);
+		let position = pool
+			.iter()
+			.position(|(_, weight)| {
+				if target < *weight as u64 { return true; }
+				target -= *weight as u64;
+				false
+			})
+			.unwrap_or(pool.len() - 1);
+		let (who, weight) = pool.remove(position);
+		total = total.saturating_sub(weight as u64);
+		ranking.push(who);
+	}
+	ranking
+}
+
+/// Draws a panel of `seats` candidates meeting every quota, followed by the other candidates as alternates.
+/// Candidates are taken in a uniformly random order, those of the quota furthest from its minimum first,
+/// and skipped when they would exceed a maximum. A few random orders are tried before giving up.
+/// Alternates replace jurors in the order drawn, whatever their attributes.
+pub fn stratified_shuffle<H: Hash, C: Clone>(
+	seed: &H::Output,
+	candidates: &[(C, Vec<(AttributeKey, AttributeValue)>)],
+	quotas: &[Quota],
+	seats: usize,
+) -> Option<Vec<C>> {
+	let target = seats.min(candidates.len());
+
+	// A minimum above the panel or above the candidates holding the value can never be met
+	for quota in quotas {
+		let available = candidates.iter().filter(|(_, attributes)| quota.counts(attributes)).count();
+		if quota.min as usize > target.min(available) { return None; }
+	}
+
+	let positions: Vec<usize> = (0..candidates.len()).collect();
+	for attempt in 0..STRATIFIED_ATTEMPTS {
+		let order = shuffle::<H, usize>(&H::hash_of(&(seed, attempt)), &positions);
+		let mut counts: Vec<usize> = quotas.iter().map(|_| 0).collect();
+		let mut picked: Vec<bool> = candidates.iter().map(|_| false).collect();
+		let mut panel: Vec<usize> = Vec::with_capacity(target);
+
+		while panel.len() < target {
+			let fits = |position: usize, picked: &[bool], counts: &[usize]| {
+				!picked[position] && quotas.iter().zip(counts).all(|(quota, count)| {
+					!quota.counts(&candidates[position].1) || *count < quota.max as usize
+				})
+			};
+			// The quota with the fewest spare candidates for the jurors it still misses goes first
+			let urgent = quotas
+				.iter()
+				.zip(counts.iter())
+				.filter(|(quota, count)| **count < quota.min as usize)
+				.min_by_key(|(quota, count)| {
+					let available = order
+						.iter()
+						.filter(|position| fits(**position, &picked, &counts) && quota.counts(&candidates[**position].1))
+						.count();
+					available.saturating_sub(quota.min as usize - **count)
+				})
+				.map(|(quota, _)| quota);
+			let next = order.iter().copied().find(|position| {
+				fits(*position, &picked, &counts) && urgent.map_or(true, |quota| quota.counts(&candidates[*position].1))
+			});
+			match next {
+				Some(position) => {
+					picked[position] = true;
+					for (quota, count) in quotas.iter().zip(counts.iter_mut()) {
+						if quota.counts(&candidates[position].1) { *count += 1; }
+					}
+					panel.push(position);
+				},
+				None => break,
+			}
+		}
+
+		let complete = panel.len() == target &&
+			quotas.iter().zip(counts.iter()).all(|(quota, count)| *count >= quota.min as usize);
+		if complete {
+			let alternates = order.into_iter().filter(|position| !picked[*position]);
+			return Some(panel.into_iter().chain(alternates).map(|position| candidates[position].0.clone()).collect());
+		}
+	}
+	None
+}
+
+/// Reproduces the allotment machine of Athens. The tokens of every tribe are put in random order
+/// into a column of slots, the column of tribe `i` being shuffled with the hash of (seed, i).
+/// Rows with an empty slot are skipped. For every other row, a die falls from a tube holding
+/// `seats` white dice and black ones for the rest: a white die selects the whole row.
+/// Returns the ranking of every column, the tokens of the white rows first, then those of the
+/// black rows and of the skipped rows, and the dice in the order they fell, `true` for white.
+pub fn athenian<H: Hash, C: Clone>(seed: &H::Output, columns: &[Vec<C>], seats: usize) -> (Vec<Vec<C>>, Vec<bool>) {
+	let columns: Vec<Vec<C>> = columns
+		.iter()
+		.enumerate()
+		.map(|(index, column)| shuffle::<H, C>(&H::hash_of(&(seed, index as u16)), column))
+		.collect();
+	let rows = columns.iter().map(|column| column.len()).min().unwrap_or(0);
+
+	let tube: Vec<bool> = (0..rows).map(|row| row < seats).collect();
+	let dice = shuffle::<H, bool>(&H::hash_of(&(seed, DICE_SUBJECT)), &tube);
+
+	let rankings = columns
+		.into_iter()
+		.map(|column| {
+			let white = dice.iter().zip(column.iter()).filter(|(die, _)| **die).map(|(_, token)| token.clone());
+			let black = dice.iter().zip(column.iter()).filter(|(die, _)| !**die).map(|(_, token)| token.clone());
+			white.chain(black).chain(column[rows..].iter().cloned()).collect()
+		})
+		.collect();
+	(rankings, dice)
+}
+
+/// Returns a number uniformly distributed in [0, bound) derived from the seed.
+/// Values of the incomplete last interval are rejected to avoid the modulo bias.
+pub fn random_below<H: Hash>(seed: &H::Output, nonce: &mut u32, bound: u64) -> u64 {
+	let bound = bound.max(1) as u128;
+	let zone = (u64::MAX as u128 + 1) / bound * bound;
+	loop {
+		let random = H::hash_of(&(seed, *nonce));
+		*nonce = nonce.wrapping_add(1);
+		let mut bytes: &[u8] = random.as_ref();
+		let value = u64::decode(&mut bytes).unwrap_or_default() as u128;
+		if value < zone {
+			return (value % bound) as u64;
+		}
+	}
+}
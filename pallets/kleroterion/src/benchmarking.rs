@@ -8,17 +8,18 @@ const UX_TS_20300101: u64 = 1893452400;
 #[allow(unused)]
 use crate::Pallet as Kleroterion;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{sp_runtime::traits::Bounded, traits::Currency};
 use frame_system::RawOrigin;
 use sp_std::vec;
 
 
 benchmarks! {
-	// Trigger 100 Open Jury Calls
+	// Open a Jury Call of two tribes, the caller can afford its deposit
 	bench_open_jury_call_100  {
-		let s in 0 .. 100;
 		let caller: T::AccountId = whitelisted_caller();
-		let tribes = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
-	}: 	open_jury_call(RawOrigin::Signed(caller), tribes.clone(), 1, UX_TS_20300101)
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let tribes = vec![(b"Tribe1".to_vec(), 1), (b"Tribe2".to_vec(), 1)];
+	}: 	open_jury_call(RawOrigin::Signed(caller), tribes.clone(), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0u32.into(), 0u32.into(), false, None)
 }
 
 impl_benchmark_test_suite!(Kleroterion, crate::mock::new_test_ext(), crate::mock::Test);
@@ -10,6 +10,8 @@ mod tests;
 
 pub mod types;
 
+pub mod migrations;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
@@ -19,33 +21,275 @@ pub mod pallet {
 	use frame_support::{
 			dispatch::DispatchResult, 
 			pallet_prelude::*,
-			traits::UnixTime,
+			traits::{BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency, StorageVersion, UnixTime},
+			PalletId, transactional,
 		};
-	use frame_support::sp_runtime::traits::Printable;
+	use frame_support::sp_runtime::traits::{AccountIdConversion, Hash, Printable, Saturating, Zero};
 	use frame_support::sp_runtime::print;
 	use frame_system::pallet_prelude::*;
-	use sp_std::vec::Vec;
+	use sp_std::{convert::TryInto, vec::Vec};
+	use kleroterion_sortition as sortition;
+	pub use kleroterion_sortition::Quota;
 	pub use crate::types::{
 		JuryCallID,
 		Selections,
 		Candidates,
+		TribeIndex,
+		CandidateWeight,
+		AttributeKey,
+		AttributeValue,
+		RegistrarIndex,
+		AlgorithmVersion,
+		WeightSource,
+		BalanceWeight,
+		IdentityProvider,
 	};
 
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
+	pub(crate) type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
+
+	/// The current storage version, see the migrations module.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+	/// Subject mixed into the randomness requested for a draw.
+	const DRAW_SUBJECT: &[u8] = b"kleroterion/draw";
+
+	/// Weight of a hash computed by a draw algorithm.
+	const DRAW_HASH_WEIGHT: Weight = 1_000_000;
+
+	/// Weight of checking an attribute of a candidate against a quota in a stratified draw.
+	const DRAW_COMPARISON_WEIGHT: Weight = 5_000;
+
+	/// Version of the draw algorithm given to new Jury Calls, see the kleroterion-sortition crate.
+	pub const DRAW_ALGORITHM_VERSION: AlgorithmVersion = sortition::LATEST_VERSION;
+
+	// The candidates of a tribe are kept in their own storage maps, see TribeCandidates
 	#[derive(Clone, Encode, Decode, PartialEq)]
 	pub struct Tribe<T: Config> {
-		pub name:  Vec<u8>,
-		pub candidate_count: Candidates, //indicates the current number of candidates 
-		pub candidates: Vec<AccountOf<T>>, // Vector containing the candidates
+		pub name:  BoundedVec<u8, T::MaxTribeNameLen>,
+		pub seats: Selections, // number of jurors drawn in this tribe
+	}
+
+	impl<T: Config> MaxEncodedLen for Tribe<T> {
+		fn max_encoded_len() -> usize {
+			BoundedVec::<u8, T::MaxTribeNameLen>::max_encoded_len()
+				.saturating_add(Selections::max_encoded_len())
+		}
+	}
+
+	/// Designates a tribe of a Jury Call, either by its position or by its name.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+	pub enum TribeSelector {
+		Index(TribeIndex),
+		Name(Vec<u8>),
+	}
+
+	/// How the jurors of a Jury Call are drawn among the candidates of each tribe.
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub enum DrawMode {
+		/// Every candidate has the same chance.
+		Uniform,
+		/// Candidates are drawn without replacement, proportionally to the weight recorded at registration.
+		Weighted,
+		/// Candidates declare attribute values and every tribe's panel must meet the quotas of the Jury Call.
+		Stratified,
+		/// The allotment machine of Athens: the tokens of each tribe fill a column of slots and black and white
+		/// dice select whole rows. Every tribe has the same number of seats and the dice are recorded with the draw.
+		Athenian,
+	}
+
+	impl Default for DrawMode {
+		fn default() -> Self {
+			DrawMode::Uniform
+		}
+	}
+
+	/// Positive judgements a registrar can give to an identity, from the weakest to the strongest.
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+	pub enum JudgementLevel {
+		/// The registrar found the identity information reasonable.
+		Reasonable,
+		/// The registrar checked the identity information.
+		KnownGood,
+	}
+
+	/// Identity the candidates of a Jury Call must hold to stand and to be drawn.
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub struct IdentityRequirement {
+		/// The registrar whose judgement counts.
+		pub registrar: RegistrarIndex,
+		/// The weakest judgement accepted.
+		pub judgement: JudgementLevel,
+	}
+
+	impl IdentityRequirement {
+		/// Returns true if the account holds an identity judged good enough by the registrar
+		pub fn is_met<T: Config>(&self, who: &AccountOf<T>) -> bool {
+			T::IdentityProvider::judgement(who, self.registrar)
+				.map_or(false, |judgement| judgement >= self.judgement)
+		}
+	}
+
+	/// Everything needed to replay the draw of a Jury Call, see Pallet::replay_draw.
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct DrawRecord<T: Config> {
+		/// Seed of the draw, each tribe is ranked with the hash of (seed, tribe_index).
+		pub seed: T::Hash,
+		/// Block the seed was taken from.
+		pub block: T::BlockNumber,
+		/// Timestamp of the draw, the challenge period starts then.
+		pub drawn_at: u64,
+		/// Dice of an Athenian draw in the order they fell, true for white. Empty in the other draw modes.
+		pub dice: BoundedVec<bool, T::MaxCandidatesPerTribe>,
+		/// Version of the draw algorithm.
+		pub algorithm_version: AlgorithmVersion,
+		/// Hash of the candidates of every tribe, in the order they were given to the draw.
+		pub candidates_hash: T::Hash,
+	}
+
+	impl<T: Config> MaxEncodedLen for DrawRecord<T> {
+		fn max_encoded_len() -> usize {
+			T::Hash::max_encoded_len()
+				.saturating_mul(2)
+				.saturating_add(T::BlockNumber::max_encoded_len())
+				.saturating_add(u64::max_encoded_len())
+				.saturating_add(BoundedVec::<bool, T::MaxCandidatesPerTribe>::max_encoded_len())
+				.saturating_add(AlgorithmVersion::max_encoded_len())
+		}
+	}
+
+	/// Lifecycle of a Jury Call.
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub enum JuryCallStatus {
+		/// Candidates can register and withdraw.
+		Registering,
+		/// Registration is over, the lot can be drawn.
+		ReadyToDraw,
+		/// The jurors have been drawn.
+		Drawn,
+		/// The Jury Call is over.
+		Closed,
+		/// The Jury Call was abandoned before the draw.
+		Cancelled,
+	}
+
+	impl Default for JuryCallStatus {
+		fn default() -> Self {
+			JuryCallStatus::Registering
+		}
+	}
+
+	impl JuryCallStatus {
+		/// Returns true if a Jury Call can move from this status to the next one
+		pub fn can_become(&self, next: JuryCallStatus) -> bool {
+			use JuryCallStatus::*;
+			matches!(
+				(self, next),
+				(Registering, ReadyToDraw) |
+				(Registering, Cancelled) |
+				(ReadyToDraw, Drawn) |
+				(ReadyToDraw, Cancelled) |
+				(Drawn, ReadyToDraw) |
+				(Drawn, Closed)
+			)
+		}
+	}
+
+	/// Answer of a drawn juror to their summons.
+	#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub enum SummonsStatus {
+		/// Waiting for the juror to accept or decline.
+		Pending,
+		/// The juror accepted to serve.
+		Accepted,
+		/// The juror declined and has been replaced.
+		Declined,
+		/// The juror did not answer in time and has been replaced.
+		TimedOut,
+		/// The juror accepted but did not attend, as reported by the owner.
+		Absent,
+		/// The juror completed their service, as confirmed by the owner.
+		Served,
+		/// The juror was found ineligible on a challenge and has been replaced.
+		Voided,
+	}
+
+	/// Summons sent to a drawn juror.
+	#[derive(Clone, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub struct Summons {
+		pub tribe: TribeIndex,
+		/// The juror must answer before this timestamp.
+		pub deadline: u64,
+		pub status: SummonsStatus,
+	}
+
+	/// What a challenge contests in a draw.
+	#[derive(Clone, Encode, Decode, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub enum ChallengeReason<AccountId> {
+		/// The juror should not have been drawn. Upheld, the juror is replaced by the next reserve.
+		IneligibleJuror(AccountId),
+		/// The draw was not seeded as it should have been. Upheld, the whole draw is voided and the lot drawn again.
+		WrongSeed,
+	}
+
+	/// Challenge submitted against the draw of a Jury Call.
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct Challenge<T: Config> {
+		pub reason: ChallengeReason<AccountOf<T>>,
+		/// Amount reserved from the challenger, slashed if the challenge is rejected.
+		pub bond: BalanceOf<T>,
+	}
+
+	impl<T: Config> MaxEncodedLen for Challenge<T> {
+		fn max_encoded_len() -> usize {
+			ChallengeReason::<AccountOf<T>>::max_encoded_len()
+				.saturating_add(BalanceOf::<T>::max_encoded_len())
+		}
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq)]
 	pub struct JuryCall<T: Config> {
-		pub tribes:  Vec<Tribe<T>>,
-		pub selections: Selections,
+		pub tribes:  BoundedVec<Tribe<T>, T::MaxTribes>,
 		// Tribes+Candidates
-		pub start_after: u64,
+		/// Candidates can register from this timestamp...
+		pub registration_opens: u64,
+		/// ...until this one, excluded.
+		pub registration_closes: u64,
+		/// The lot cannot be drawn before this timestamp.
+		pub draw_after: u64,
+		pub draw_mode: DrawMode,
+		/// Version of the draw algorithm, fixed at opening so that runtime upgrades never change the draw.
+		pub algorithm: AlgorithmVersion,
+		/// Identity required from the candidates, if any.
+		pub identity: Option<IdentityRequirement>,
+		/// Amount reserved from every candidate until they are released or slashed.
+		pub candidate_bond: BalanceOf<T>,
+		/// Amount paid to every juror who completes their service, out of the escrow funded by the owner.
+		pub juror_compensation: BalanceOf<T>,
+		/// Amount reserved from the owner for the storage of the Jury Call, until it is removed.
+		pub deposit: BalanceOf<T>,
+		/// Candidates commit to a secret while registering and reveal it before the draw,
+		/// the draw is then seeded with the revealed secrets instead of T::Randomness.
+		pub commit_reveal: bool,
+		/// Jury Call whose accepted jurors are the candidates of this one, which is drawn once the first stage is closed.
+		pub first_stage: Option<JuryCallID>,
 		pub owner: AccountOf<T>,
+		pub status: JuryCallStatus,
+	}
+
+	impl<T: Config> MaxEncodedLen for JuryCall<T> {
+		fn max_encoded_len() -> usize {
+			BoundedVec::<Tribe<T>, T::MaxTribes>::max_encoded_len()
+				.saturating_add(u64::max_encoded_len().saturating_mul(3))
+				.saturating_add(DrawMode::max_encoded_len())
+				.saturating_add(AlgorithmVersion::max_encoded_len())
+				.saturating_add(Option::<IdentityRequirement>::max_encoded_len())
+				.saturating_add(BalanceOf::<T>::max_encoded_len().saturating_mul(3))
+				.saturating_add(bool::max_encoded_len())
+				.saturating_add(Option::<JuryCallID>::max_encoded_len())
+				.saturating_add(<AccountOf<T>>::max_encoded_len())
+				.saturating_add(JuryCallStatus::max_encoded_len())
+		}
 	}
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -53,12 +297,95 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type TimeProvider: UnixTime;  //Cf https://stackoverflow.com/questions/68262293/substrate-frame-v2-how-to-use-pallet-timestamp
+		/// Source of randomness used to draw the jurors.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// Gives the weight of a candidate in the Jury Calls drawn with DrawMode::Weighted.
+		type WeightSource: WeightSource<Self::AccountId>;
+		/// Gives the registrar judgements on the identity of the candidates.
+		type IdentityProvider: IdentityProvider<Self::AccountId>;
+		/// Currency in which candidate bonds are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Deposit reserved from the owner of every Jury Call.
+		#[pallet::constant]
+		type JuryCallDepositBase: Get<BalanceOf<Self>>;
+		/// Deposit reserved from the owner of a Jury Call for each of its tribes.
+		#[pallet::constant]
+		type TribeDeposit: Get<BalanceOf<Self>>;
+		/// Deposit reserved from the owner of a Jury Call for each byte of its tribe names.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+		/// Identifier of the account holding the compensation escrows.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Time given to a drawn juror to accept or decline their summons, in seconds.
+		#[pallet::constant]
+		type SummonsPeriod: Get<u64>;
+		/// Time during which the draw of a Jury Call can be challenged, in seconds.
+		#[pallet::constant]
+		type ChallengePeriod: Get<u64>;
+		/// Amount reserved from the author of a challenge until it is resolved.
+		#[pallet::constant]
+		type ChallengeBond: Get<BalanceOf<Self>>;
+		/// Origin allowed to resolve challenges, besides root.
+		type ChallengeOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum number of tribes in a Jury Call.
+		#[pallet::constant]
+		type MaxTribes: Get<u32>;
+		/// Maximum length of a tribe name, in bytes.
+		#[pallet::constant]
+		type MaxTribeNameLen: Get<u32>;
+		/// Maximum number of candidates in a tribe.
+		#[pallet::constant]
+		type MaxCandidatesPerTribe: Get<u32>;
+		/// Maximum length of the reason given for cancelling a Jury Call, in bytes.
+		#[pallet::constant]
+		type MaxReasonLen: Get<u32>;
+		/// Maximum number of quotas of a stratified Jury Call.
+		#[pallet::constant]
+		type MaxQuotas: Get<u32>;
+		/// Maximum number of attributes a candidate can declare.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::migrate::<T>()
+		}
+
+		fn integrity_test() {
+			// A draw happens in a single Normal extrinsic, whatever the number of candidates
+			let block_weights = T::BlockWeights::get();
+			let max_extrinsic = block_weights.get(DispatchClass::Normal).max_extrinsic.unwrap_or(block_weights.max_block);
+			assert!(
+				draw_jurors_weight::<T>() <= max_extrinsic,
+				"The worst-case draw does not fit in an extrinsic, lower MaxTribes, MaxCandidatesPerTribe, MaxQuotas or MaxAttributes",
+			);
+			// So is the removal of everything a Jury Call stores
+			assert!(
+				clear_weight::<T>(7, 4, 3) <= max_extrinsic,
+				"Clearing a Jury Call does not fit in an extrinsic, lower MaxTribes or MaxCandidatesPerTribe",
+			);
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			crate::migrations::pre_migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			crate::migrations::post_migrate::<T>()
+		}
+	}
+
 	// The pallet's runtime storage items.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/storage
 
@@ -76,6 +403,102 @@ pub mod pallet {
 
 
 
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_weight)]
+	/// Stores the candidates registered in each tribe of a Jury Call, with their weight at registration.
+	pub(super) type TribeCandidates<T: Config> = StorageDoubleMap<_, Twox64Concat, (JuryCallID, TribeIndex), Blake2_128Concat, AccountOf<T>, CandidateWeight>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_count)]
+	/// Indicates the current number of candidates of each tribe of a Jury Call.
+	pub(super) type CandidateCount<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Twox64Concat, TribeIndex, Candidates, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candidacy)]
+	/// Stores the tribe in which an account is a candidate, for each Jury Call.
+	pub(super) type Candidacies<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, TribeIndex>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bond)]
+	/// Stores the bond reserved from each candidate of a Jury Call who has not been released or slashed yet.
+	pub(super) type Bonds<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, BalanceOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	/// Stores what is left of the compensation budget of a Jury Call, held by the pallet account.
+	pub(super) type Escrows<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, BalanceOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	/// Stores the hash of (account, secret) committed by each candidate of a commit-reveal Jury Call.
+	pub(super) type Commitments<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn revealed_secret)]
+	/// Stores the secrets revealed by the candidates of a commit-reveal Jury Call.
+	pub(super) type Reveals<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, T::Hash>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn quotas)]
+	/// Stores the quotas every tribe's panel must meet in a stratified Jury Call.
+	pub(super) type Quotas<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, BoundedVec<Quota, T::MaxQuotas>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_attributes)]
+	/// Stores the attribute values declared by the candidates of a stratified Jury Call.
+	pub(super) type CandidateAttributes<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, BoundedVec<(AttributeKey, AttributeValue), T::MaxAttributes>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn selected_jurors)]
+	/// Stores the jurors drawn for each tribe of a Jury Call.
+	pub(super) type SelectedJurors<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Twox64Concat, TribeIndex, BoundedVec<AccountOf<T>, T::MaxCandidatesPerTribe>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ranking)]
+	/// Stores the random order of all the candidates of a tribe, as drawn.
+	/// The first ones are the jurors, the others are the alternates, in order.
+	pub(super) type Rankings<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Twox64Concat, TribeIndex, BoundedVec<AccountOf<T>, T::MaxCandidatesPerTribe>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn draw_record)]
+	/// Stores the inputs of the draw of a Jury Call.
+	pub(super) type DrawRecords<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, DrawRecord<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_cursor)]
+	/// Position in the ranking of the next alternate to summon when a juror is replaced.
+	pub(super) type ReserveCursor<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Twox64Concat, TribeIndex, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn summons)]
+	/// Stores the summons of every juror drawn in a Jury Call.
+	pub(super) type JurorSummons<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Twox64Concat, AccountOf<T>, Summons>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenge)]
+	/// Stores the unresolved challenges against the draw of a Jury Call, by challenger.
+	pub(super) type Challenges<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, AccountOf<T>, Challenge<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_count)]
+	/// Indicates the current number of unresolved challenges against the draw of a Jury Call.
+	pub(super) type ChallengeCount<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn challenger_of)]
+	/// Stores the author of the unresolved challenge contesting each reason in the draw of a Jury Call.
+	pub(super) type ChallengedReasons<T: Config> = StorageDoubleMap<_, Twox64Concat, JuryCallID, Blake2_128Concat, ChallengeReason<AccountOf<T>>, AccountOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn draw_voids)]
+	/// Indicates how many times the draw of a Jury Call has been voided, so that every new draw gets its own seed.
+	pub(super) type DrawVoids<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn second_stage_count)]
+	/// Indicates the number of second stages drawing among the jurors of a Jury Call that are neither closed nor cancelled.
+	pub(super) type SecondStageCount<T: Config> = StorageMap<_, Twox64Concat, JuryCallID, u32, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn jury_call_cnt)]
 	pub(super) type JuryCallCnt<T: Config> = StorageValue<_, JuryCallID, ValueQuery>;
@@ -83,11 +506,65 @@ pub mod pallet {
 	// Pallets use events to inform users when important changes are made.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/events
 	#[pallet::event]
-	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A Jury Call has been opened. [jury_call_id, who]
 		JuryCallOpened(JuryCallID, T::AccountId),
+		/// A candidate registered in a tribe of a Jury Call. [jury_call_id, tribe_index, who]
+		CandidateRegistered(JuryCallID, TribeIndex, T::AccountId),
+		/// A candidate withdrew from a tribe of a Jury Call. [jury_call_id, tribe_index, who]
+		CandidacyWithdrawn(JuryCallID, TribeIndex, T::AccountId),
+		/// Jurors have been drawn in a tribe of a Jury Call. [jury_call_id, tribe_index, jurors]
+		JurorsSelected(JuryCallID, TribeIndex, Vec<T::AccountId>),
+		/// A candidate no longer held the required identity and was left out of the draw. [jury_call_id, tribe_index, who]
+		CandidateExcluded(JuryCallID, TribeIndex, T::AccountId),
+		/// The status of a Jury Call changed. [jury_call_id, previous, current]
+		StatusChanged(JuryCallID, JuryCallStatus, JuryCallStatus),
+		/// A Jury Call has been cancelled before its draw. [jury_call_id, reason]
+		JuryCallCancelled(JuryCallID, Vec<u8>),
+		/// A drawn juror accepted their summons. [jury_call_id, who]
+		SummonsAccepted(JuryCallID, T::AccountId),
+		/// A drawn juror declined their summons. [jury_call_id, who]
+		SummonsDeclined(JuryCallID, T::AccountId),
+		/// A drawn juror did not answer their summons in time. [jury_call_id, who]
+		SummonsTimedOut(JuryCallID, T::AccountId),
+		/// A juror who accepted their summons did not attend. [jury_call_id, who]
+		JurorAbsent(JuryCallID, T::AccountId),
+		/// The bond of a juror who failed to serve went to the owner. [jury_call_id, who, amount]
+		BondSlashed(JuryCallID, T::AccountId, BalanceOf<T>),
+		/// The owner locked the compensation budget of a Jury Call in escrow. [jury_call_id, amount]
+		CompensationLocked(JuryCallID, BalanceOf<T>),
+		/// A juror who completed their service has been paid. [jury_call_id, who, amount]
+		JurorPaid(JuryCallID, T::AccountId, BalanceOf<T>),
+		/// What was left of the compensation budget went back to the owner. [jury_call_id, owner, amount]
+		CompensationRefunded(JuryCallID, T::AccountId, BalanceOf<T>),
+		/// A closed or cancelled Jury Call has been removed and its deposit returned. [jury_call_id, owner, deposit]
+		JuryCallRemoved(JuryCallID, T::AccountId, BalanceOf<T>),
+		/// A juror has been replaced by the next reserve of their tribe. [jury_call_id, tribe_index, replaced, replacement]
+		JurorReplaced(JuryCallID, TribeIndex, T::AccountId, T::AccountId),
+		/// The owner set the quotas of a stratified Jury Call. [jury_call_id]
+		QuotasSet(JuryCallID),
+		/// A candidate declared their attribute values. [jury_call_id, who]
+		AttributesSet(JuryCallID, T::AccountId),
+		/// A candidate committed to a secret. [jury_call_id, who]
+		SecretCommitted(JuryCallID, T::AccountId),
+		/// A candidate revealed their secret. [jury_call_id, who]
+		SecretRevealed(JuryCallID, T::AccountId),
+		/// An account challenged the draw of a Jury Call. [jury_call_id, challenger]
+		ChallengeSubmitted(JuryCallID, T::AccountId),
+		/// A challenge has been upheld and the challenger got their bond back. [jury_call_id, challenger]
+		ChallengeUpheld(JuryCallID, T::AccountId),
+		/// A challenge has been rejected and the challenger lost their bond. [jury_call_id, challenger, bond]
+		ChallengeRejected(JuryCallID, T::AccountId, BalanceOf<T>),
+		/// A challenge became pointless when the draw was voided, the challenger got their bond back. [jury_call_id, challenger]
+		ChallengeDropped(JuryCallID, T::AccountId),
+		/// A juror found ineligible on a challenge lost their seat. [jury_call_id, tribe_index, juror]
+		JurorVoided(JuryCallID, TribeIndex, T::AccountId),
+		/// The draw of a Jury Call has been voided on a challenge, the lot can be drawn again. [jury_call_id]
+		DrawVoided(JuryCallID),
+		/// The accepted jurors of the first stage became the candidates of a second stage. [jury_call_id, first_stage_id]
+		CandidatePoolImported(JuryCallID, JuryCallID),
 	}
 
 	// Errors inform users that something went wrong.
@@ -99,10 +576,138 @@ pub mod pallet {
 		JuryCallCntOverflow,
 		/// Cannot use tTwo identical tribe names.
 		DuplicateTribes,
-		/// Selections must be greater than zero
+		/// A Jury Call cannot have more than MaxTribes tribes
+		TooManyTribes,
+		/// A tribe name cannot be longer than MaxTribeNameLen
+		TribeNameTooLong,
+		/// A tribe cannot have more than MaxCandidatesPerTribe candidates
+		TooManyCandidates,
+		/// A cancellation reason cannot be longer than MaxReasonLen
+		ReasonTooLong,
+		/// The weight source gives no weight to this account
+		ZeroWeight,
+		/// The account does not hold the identity required by the Jury Call
+		IdentityNotVerified,
+		/// The account cannot afford the candidate bond
+		InsufficientBond,
+		/// Only a juror who accepted their summons can be reported absent or confirmed
+		SummonsNotAccepted,
+		/// The owner cannot afford the compensation budget
+		InsufficientCompensationFunds,
+		/// The owner cannot afford the deposit of the Jury Call
+		InsufficientDeposit,
+		/// Only a closed or cancelled Jury Call can be removed
+		JuryCallNotFinished,
+		/// The Jury Call has no commit-reveal phase
+		NotCommitReveal,
+		/// A commit-reveal Jury Call needs time between Registration_Closes and Draw_After for the reveals
+		NoRevealWindow,
+		/// A commit-reveal Jury Call needs a candidate bond, so that withholding a secret has a cost
+		NoRevealBond,
+		/// The candidate already committed to a secret
+		AlreadyCommitted,
+		/// The candidate did not commit to a secret
+		NoCommitment,
+		/// Registration is open until Registration_Closes
+		RegistrationNotOver,
+		/// Secrets are revealed from Registration_Closes until Draw_After
+		RevealNotOpen,
+		/// The candidate already revealed their secret
+		AlreadyRevealed,
+		/// The secret does not match the commitment
+		InvalidReveal,
+		/// No candidate revealed their secret, the draw cannot be seeded
+		NoReveals,
+		/// The runtime no longer implements the draw algorithm of the Jury Call
+		UnsupportedAlgorithm,
+		/// The draw can no longer be challenged
+		ChallengePeriodOver,
+		/// The draw can still be challenged
+		ChallengePeriodOpen,
+		/// The draw has unresolved challenges
+		ChallengePending,
+		/// The account already challenged this draw
+		AlreadyChallenged,
+		/// An unresolved challenge already contests this reason
+		ReasonAlreadyChallenged,
+		/// The challenge does not exist
+		ChallengeNotFound,
+		/// The challenger cannot afford the challenge bond
+		InsufficientChallengeBond,
+		/// Quotas and attributes only apply to stratified Jury Calls
+		NotStratified,
+		/// A Jury Call cannot have more than MaxQuotas quotas
+		TooManyQuotas,
+		/// Two quotas count the same attribute value
+		DuplicateQuota,
+		/// A quota minimum cannot exceed its maximum
+		InvalidQuota,
+		/// Quotas can only be set before the draw
+		QuotasFrozen,
+		/// A candidate cannot declare more than MaxAttributes attributes
+		TooManyAttributes,
+		/// A candidate can only declare one value per attribute
+		DuplicateAttribute,
+		/// No panel of the tribe meets every quota
+		QuotasInfeasible,
+		/// Every tribe must have at least one seat
 		ZeroSelections,
-		/// Start_After should be in the future
-		StartAfterInThePast,
+		/// The tribes of an Athenian Jury Call must have the same number of seats
+		UnequalSeats,
+		/// The first stage of the Jury Call does not exist
+		FirstStageNotFound,
+		/// The first stage of the Jury Call is cancelled, it will never have jurors
+		FirstStageCancelled,
+		/// A second stage must have as many tribes as its first stage
+		StageTribesMismatch,
+		/// A second stage cannot be commit-reveal, stratified nor bond its candidates
+		InvalidSecondStage,
+		/// The candidates of a second stage are the jurors of its first stage
+		CandidatePoolFixed,
+		/// A second stage is drawn once its first stage is closed
+		FirstStageNotClosed,
+		/// A second stage still draws among the jurors of this Jury Call
+		FirstStageInUse,
+		/// Registration_Closes should be in the future
+		RegistrationClosesInThePast,
+		/// Registration_Opens should be before Registration_Closes
+		InvalidRegistrationWindow,
+		/// Draw_After cannot be before Registration_Closes
+		DrawBeforeRegistrationCloses,
+		/// No Jury Call exists with this id
+		JuryCallNotFound,
+		/// The Jury Call has no such tribe
+		TribeNotFound,
+		/// Registration has not opened yet
+		RegistrationNotOpen,
+		/// Registration is closed once Registration_Closes is reached
+		RegistrationClosed,
+		/// The account is already a candidate in a tribe of this Jury Call
+		AlreadyRegistered,
+		/// Arithmetic overflow when incrementing the candidate counter of a tribe.
+		CandidateCountOverflow,
+		/// The account is not a candidate of this Jury Call
+		NotACandidate,
+		/// Candidacies can only be withdrawn before Registration_Closes
+		WithdrawalClosed,
+		/// The lot cannot be drawn before Draw_After
+		DrawTooEarly,
+		/// The jurors of this Jury Call have already been drawn
+		AlreadyDrawn,
+		/// The Jury Call cannot move from its current status to the requested one
+		InvalidStatusTransition,
+		/// Only the owner of the Jury Call can do this
+		NotOwner,
+		/// The jurors of this Jury Call have not been drawn
+		NotDrawn,
+		/// The account has not been summoned in this Jury Call
+		NotSummoned,
+		/// The summons has already been answered
+		SummonsNotPending,
+		/// The summons can no longer be accepted
+		SummonsExpired,
+		/// The juror can still answer the summons
+		SummonsNotExpired,
 		/// Error names should be descriptive.
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
@@ -115,8 +720,72 @@ pub mod pallet {
 			Error::BadOrigin => "BadOrigin".print(),  
 			Error::JuryCallCntOverflow => "Jury Call Value Overflowed".print(),
 			Error::DuplicateTribes => "Duplicate tribe names entered".print(),
-			Error::ZeroSelections => "Zero selections not allowed".print(),
-			Error::StartAfterInThePast => "Start After must be in the future".print(),
+			Error::TooManyTribes => "Too many tribes".print(),
+			Error::TribeNameTooLong => "Tribe name too long".print(),
+			Error::TooManyCandidates => "Too many candidates in the tribe".print(),
+			Error::ReasonTooLong => "Cancellation reason too long".print(),
+			Error::ZeroWeight => "Candidate has no weight".print(),
+			Error::IdentityNotVerified => "Identity not verified".print(),
+			Error::InsufficientBond => "Cannot reserve the candidate bond".print(),
+			Error::SummonsNotAccepted => "Summons not accepted".print(),
+			Error::InsufficientCompensationFunds => "Cannot fund the juror compensation".print(),
+			Error::InsufficientDeposit => "Cannot reserve the Jury Call deposit".print(),
+			Error::JuryCallNotFinished => "Jury Call not closed nor cancelled".print(),
+			Error::NotCommitReveal => "Jury Call has no commit-reveal phase".print(),
+			Error::NoRevealWindow => "Draw After must be after Registration Closes to reveal".print(),
+			Error::NoRevealBond => "Commit-reveal needs a candidate bond".print(),
+			Error::AlreadyCommitted => "Secret already committed".print(),
+			Error::NoCommitment => "No secret committed".print(),
+			Error::RegistrationNotOver => "Registration not over".print(),
+			Error::RevealNotOpen => "Reveal phase not open".print(),
+			Error::AlreadyRevealed => "Secret already revealed".print(),
+			Error::InvalidReveal => "Secret does not match the commitment".print(),
+			Error::NoReveals => "No secret revealed".print(),
+			Error::UnsupportedAlgorithm => "Draw algorithm not supported".print(),
+			Error::ChallengePeriodOver => "Challenge period over".print(),
+			Error::ChallengePeriodOpen => "Challenge period still open".print(),
+			Error::ChallengePending => "Challenge pending".print(),
+			Error::AlreadyChallenged => "Draw already challenged by this account".print(),
+			Error::ReasonAlreadyChallenged => "Reason already challenged".print(),
+			Error::ChallengeNotFound => "Challenge not found".print(),
+			Error::InsufficientChallengeBond => "Insufficient balance for the challenge bond".print(),
+			Error::NotStratified => "Jury Call is not stratified".print(),
+			Error::TooManyQuotas => "Too many quotas".print(),
+			Error::DuplicateQuota => "Duplicate quotas entered".print(),
+			Error::InvalidQuota => "Quota minimum above its maximum".print(),
+			Error::QuotasFrozen => "Quotas can no longer change".print(),
+			Error::TooManyAttributes => "Too many attributes".print(),
+			Error::DuplicateAttribute => "Duplicate attributes entered".print(),
+			Error::QuotasInfeasible => "Quotas cannot be met".print(),
+			Error::ZeroSelections => "Every tribe must have at least one seat".print(),
+			Error::UnequalSeats => "Tribes of an Athenian Jury Call must have the same number of seats".print(),
+			Error::FirstStageNotFound => "First stage not found".print(),
+			Error::FirstStageCancelled => "First stage cancelled".print(),
+			Error::StageTribesMismatch => "Second stage tribes do not match the first stage".print(),
+			Error::InvalidSecondStage => "Second stage cannot be commit-reveal, stratified nor bonded".print(),
+			Error::CandidatePoolFixed => "Candidates come from the first stage".print(),
+			Error::FirstStageNotClosed => "First stage not closed".print(),
+			Error::FirstStageInUse => "Second stage still drawing from this Jury Call".print(),
+			Error::RegistrationClosesInThePast => "Registration Closes must be in the future".print(),
+			Error::InvalidRegistrationWindow => "Registration Opens must be before Registration Closes".print(),
+			Error::DrawBeforeRegistrationCloses => "Draw After must not be before Registration Closes".print(),
+			Error::JuryCallNotFound => "Jury Call not found".print(),
+			Error::TribeNotFound => "Tribe not found".print(),
+			Error::RegistrationNotOpen => "Registration is not open yet".print(),
+			Error::RegistrationClosed => "Registration is closed".print(),
+			Error::AlreadyRegistered => "Candidate already registered".print(),
+			Error::CandidateCountOverflow => "Candidate Count Value Overflowed".print(),
+			Error::NotACandidate => "Not a candidate".print(),
+			Error::WithdrawalClosed => "Withdrawal is closed".print(),
+			Error::DrawTooEarly => "Draw not allowed before Draw After".print(),
+			Error::AlreadyDrawn => "Jurors already drawn".print(),
+			Error::InvalidStatusTransition => "Invalid Jury Call status transition".print(),
+			Error::NotOwner => "Not the Jury Call owner".print(),
+			Error::NotDrawn => "Jurors not drawn".print(),
+			Error::NotSummoned => "Not summoned".print(),
+			Error::SummonsNotPending => "Summons already answered".print(),
+			Error::SummonsExpired => "Summons expired".print(),
+			Error::SummonsNotExpired => "Summons not expired".print(),
 			_ => "Invalid Error Case".print(),
 		  }
 		}
@@ -129,30 +798,85 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 
 		// The tribe names are passed here as 'str' under the form of Vec<u8>.
-		// As multiple tribes can be passed, we pass Vec<Vec<u8>>
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn open_jury_call(origin: OriginFor<T>, tribe_names: Vec<Vec<u8>>, selections: Selections, start_after: u64) -> DispatchResult {
+		// As multiple tribes can be passed, we pass Vec<(Vec<u8>, Selections)>
+		// where each tribe name comes with the number of jurors drawn in that tribe.
+		// Candidates register in [registration_opens, registration_closes), the lot is drawn from draw_after.
+		// The draw_mode tells whether the candidates are drawn uniformly, by weight or by quotas.
+		// When identity is set, candidates must hold an identity judged by the given registrar.
+		// Every candidate has candidate_bond reserved until they are released or slashed.
+		// The owner pays juror_compensation for every seat into escrow, the remainder is refunded on close or cancel.
+		// A deposit growing with the tribes and their names is reserved from the owner until the Jury Call is removed.
+		// With commit_reveal, candidates reveal their secrets in [registration_closes, draw_after) to seed the draw,
+		// and the candidate_bond cannot be zero.
+		// Reads the first stage, the counter and the time, writes the counter, the Jury Call, the escrow and
+		// the second stage count, and reserving the deposit and transferring the budget touch 3 accounts.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,7))]
+		pub fn open_jury_call(
+			origin: OriginFor<T>,
+			tribes: Vec<(Vec<u8>, Selections)>,
+			registration_opens: u64,
+			registration_closes: u64,
+			draw_after: u64,
+			draw_mode: DrawMode,
+			identity: Option<IdentityRequirement>,
+			#[pallet::compact] candidate_bond: BalanceOf<T>,
+			#[pallet::compact] juror_compensation: BalanceOf<T>,
+			commit_reveal: bool,
+			first_stage: Option<JuryCallID>,
+		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
 			let who = ensure_signed(origin)?;
 
+			// Check the number of tribes and the length of their names before comparing them
+			ensure!(tribes.len() <= T::MaxTribes::get() as usize, Error::<T>::TooManyTribes);
+			ensure!(
+				tribes.iter().all(|(name, _)| name.len() <= T::MaxTribeNameLen::get() as usize),
+				Error::<T>::TribeNameTooLong
+			);
+
 			// Check tribes are distinct
+			let tribe_names: Vec<Vec<u8>> = tribes.iter().map(|(name, _)| name.clone()).collect();
 			if check_duplicate_tribes(&tribe_names) { Err(Error::<T>::DuplicateTribes)? }
 
-			// Check selections > 0
-			if selections == 0 { Err(Error::<T>::ZeroSelections)? }
+			// Check every tribe has seats > 0
+			if tribes.iter().any(|(_, seats)| *seats == 0) { Err(Error::<T>::ZeroSelections)? }
+
+			// Check the rows of an Athenian draw select one juror in every tribe
+			if draw_mode == DrawMode::Athenian {
+				ensure!(tribes.windows(2).all(|pair| pair[0].1 == pair[1].1), Error::<T>::UnequalSeats);
+			}
+
+			// Check a second stage draws among the jurors of a first stage of the same owner, tribe by tribe
+			if let Some(first_stage_id) = first_stage {
+				let first_stage = Self::jury_call(first_stage_id).ok_or(Error::<T>::FirstStageNotFound)?;
+				ensure!(first_stage.owner == who, Error::<T>::NotOwner);
+				ensure!(first_stage.status != JuryCallStatus::Cancelled, Error::<T>::FirstStageCancelled);
+				ensure!(first_stage.tribes.len() == tribes.len(), Error::<T>::StageTribesMismatch);
+				// The imported candidates never register, so no bond could be reserved from them
+				ensure!(
+					!commit_reveal && draw_mode != DrawMode::Stratified && candidate_bond.is_zero(),
+					Error::<T>::InvalidSecondStage
+				);
+			}
 			
 
-			// Check that timestamp is in the future compared to current blocks timestamp
+			// Check that registration closes in the future compared to current blocks timestamp
 			let time_now: u64 = T::TimeProvider::now().as_secs();
 			print(time_now);
-			print(start_after);
-			if time_now >= start_after {
-				print(Error::<T>::StartAfterInThePast);
-				Err(Error::<T>::StartAfterInThePast)? 
+			print(registration_closes);
+			if time_now >= registration_closes {
+				print(Error::<T>::RegistrationClosesInThePast);
+				Err(Error::<T>::RegistrationClosesInThePast)? 
 			}
 
+			// Check the registration window comes before the draw
+			ensure!(registration_opens < registration_closes, Error::<T>::InvalidRegistrationWindow);
+			ensure!(registration_closes <= draw_after, Error::<T>::DrawBeforeRegistrationCloses);
+			ensure!(!commit_reveal || registration_closes < draw_after, Error::<T>::NoRevealWindow);
+			ensure!(!commit_reveal || !candidate_bond.is_zero(), Error::<T>::NoRevealBond);
+
             // Assign a new id
 			let new_cnt = Self::jury_call_cnt()
 							.checked_add(1)
@@ -160,25 +884,47 @@ pub mod pallet {
 
 
             // Initialize the tribes
-			let mut tribes: Vec<Tribe<T>> = Default::default();
-			for tribe_name in tribe_names {
+			let name_bytes: u32 = tribes.iter().map(|(name, _)| name.len() as u32).sum();
+			let deposit = T::JuryCallDepositBase::get()
+				.saturating_add(T::TribeDeposit::get().saturating_mul((tribes.len() as u32).into()))
+				.saturating_add(T::ByteDeposit::get().saturating_mul(name_bytes.into()));
+			let mut new_tribes: Vec<Tribe<T>> = Default::default();
+			for (tribe_name, seats) in tribes {
 				let tribe = Tribe::<T> {
-					name: tribe_name,
-					candidate_count: 0,
-					candidates: Default::default(),
+					name: tribe_name.try_into().map_err(|_| Error::<T>::TribeNameTooLong)?,
+					seats,
 				};
-				tribes.push(tribe);
+				new_tribes.push(tribe);
 			}
 
 			let jury_call = JuryCall::<T> {
-				tribes: tribes,
-				selections: selections,
-				start_after: start_after,
+				tribes: new_tribes.try_into().map_err(|_| Error::<T>::TooManyTribes)?,
+				registration_opens,
+				registration_closes,
+				draw_after,
+				draw_mode,
+				algorithm: DRAW_ALGORITHM_VERSION,
+				identity,
+				candidate_bond,
+				juror_compensation,
+				deposit,
+				commit_reveal,
+				first_stage,
 				owner: who.clone(),
+				status: JuryCallStatus::Registering,
 			};
 
 
 
+			// Reserve the deposit and lock the compensation of every seat in escrow
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			let total_seats: u32 = jury_call.tribes.iter().map(|tribe| tribe.seats as u32).sum();
+			let budget = juror_compensation.saturating_mul(total_seats.into());
+			if T::Currency::transfer(&who, &Self::account_id(), budget, ExistenceRequirement::KeepAlive).is_err() {
+				T::Currency::unreserve(&who, deposit);
+				Err(Error::<T>::InsufficientCompensationFunds)?
+			}
+
 			// Update storage. 
             //keep track of how many jury_calls have been created
 			<JuryCallCnt<T>>::put(new_cnt);
@@ -187,13 +933,684 @@ pub mod pallet {
 			// The ID of a jury_call is the new_cnt number (do we need to store it inside the struct?)
 			<JuryCalls<T>>::insert(new_cnt, jury_call);
 
+			// The first stage cannot be removed until this second stage is over
+			if let Some(first_stage_id) = first_stage {
+				<SecondStageCount<T>>::mutate(first_stage_id, |count| *count = count.saturating_add(1));
+			}
+
+			if !budget.is_zero() {
+				<Escrows<T>>::insert(new_cnt, budget);
+			}
+
 			// Emit an event to report the Jury Call.
 			Self::deposit_event(Event::JuryCallOpened(new_cnt, who));
+			if !budget.is_zero() {
+				Self::deposit_event(Event::CompensationLocked(new_cnt, budget));
+			}
 
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
+		/// Registers the signer as a candidate in one tribe of a Jury Call.
+		/// The tribe can be designated by its index or by its name.
+		/// The weight given by T::WeightSource is recorded with the candidacy.
+		/// The candidate bond of the Jury Call is reserved from the signer.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7,5))]
+		pub fn register_candidate(origin: OriginFor<T>, jury_call_id: JuryCallID, tribe: TribeSelector) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			let tribe_index = find_tribe(&jury_call, &tribe).ok_or(Error::<T>::TribeNotFound)?;
+			ensure!(jury_call.first_stage.is_none(), Error::<T>::CandidatePoolFixed);
+
+			// Registration is only possible within the registration window
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(jury_call.status == JuryCallStatus::Registering, Error::<T>::RegistrationClosed);
+			ensure!(time_now >= jury_call.registration_opens, Error::<T>::RegistrationNotOpen);
+			ensure!(time_now < jury_call.registration_closes, Error::<T>::RegistrationClosed);
+
+			// Only accounts holding the required identity can stand
+			if let Some(requirement) = jury_call.identity {
+				ensure!(requirement.is_met::<T>(&who), Error::<T>::IdentityNotVerified);
+			}
+
+			// A candidate can only stand in one tribe of a given Jury Call
+			ensure!(!<Candidacies<T>>::contains_key(jury_call_id, &who), Error::<T>::AlreadyRegistered);
+
+			let candidate_count = Self::candidate_count(jury_call_id, tribe_index);
+			ensure!((candidate_count as u32) < T::MaxCandidatesPerTribe::get(), Error::<T>::TooManyCandidates);
+			let candidate_count = candidate_count
+							.checked_add(1)
+							.ok_or(<Error<T>>::CandidateCountOverflow)?;
+
+			// A candidate without weight could never be drawn by weight
+			let weight = T::WeightSource::weight_of(jury_call_id, &who);
+			ensure!(matches!(jury_call.draw_mode, DrawMode::Uniform | DrawMode::Athenian) || weight > 0, Error::<T>::ZeroWeight);
+
+			T::Currency::reserve(&who, jury_call.candidate_bond).map_err(|_| Error::<T>::InsufficientBond)?;
+			if !jury_call.candidate_bond.is_zero() {
+				<Bonds<T>>::insert(jury_call_id, &who, jury_call.candidate_bond);
+			}
+			<TribeCandidates<T>>::insert((jury_call_id, tribe_index), &who, weight);
+			<Candidacies<T>>::insert(jury_call_id, &who, tribe_index);
+			<CandidateCount<T>>::insert(jury_call_id, tribe_index, candidate_count);
+
+			Self::deposit_event(Event::CandidateRegistered(jury_call_id, tribe_index, who));
+
+			Ok(())
+		}
+
+		/// Removes the signer from the candidates of a Jury Call and releases their bond.
+		/// The candidates used by the draw are frozen from registration_closes onwards.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,3))]
+		pub fn withdraw_candidacy(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+
+			// The candidates used by the draw must not change
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(jury_call.status == JuryCallStatus::Registering, Error::<T>::WithdrawalClosed);
+			ensure!(time_now < jury_call.registration_closes, Error::<T>::WithdrawalClosed);
+
+			let tribe_index = Self::candidacy(jury_call_id, &who).ok_or(Error::<T>::NotACandidate)?;
+
+			<TribeCandidates<T>>::remove((jury_call_id, tribe_index), &who);
+			<Candidacies<T>>::remove(jury_call_id, &who);
+			<CandidateAttributes<T>>::remove(jury_call_id, &who);
+			<Commitments<T>>::remove(jury_call_id, &who);
+			Self::release_bond(jury_call_id, &who);
+			<CandidateCount<T>>::mutate(jury_call_id, tribe_index, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(Event::CandidacyWithdrawn(jury_call_id, tribe_index, who));
+
+			Ok(())
+		}
+
+		/// Records that registration is over, moving a Jury Call to ReadyToDraw.
+		/// Anyone can do it once registration_closes is reached.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn close_registration(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let _who = ensure_signed(origin)?;
+
+			let mut jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(time_now >= jury_call.registration_closes, Error::<T>::RegistrationNotOver);
+
+			Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::ReadyToDraw)?;
+			<JuryCalls<T>>::insert(jury_call_id, jury_call);
+
+			Ok(())
+		}
+
+		/// Ranks the candidates of every tribe of a Jury Call in a random order.
+		/// The first ones are the jurors, the others become alternates.
+		/// Anyone can trigger the draw once draw_after is reached, it can only happen once.
+		/// When a tribe has fewer candidates than seats, all its candidates are selected.
+		/// Candidates who lost the identity required since they registered are left out,
+		/// and so are those who no longer hold the candidate bond after a voided draw.
+		/// Jurors and alternates keep their bond until the Jury Call is closed, the candidates left out are released.
+		/// In a commit-reveal Jury Call, the draw is seeded with the revealed secrets and candidates
+		/// who did not reveal are left out, losing their bond if they had committed.
+		/// The inputs of the draw are stored in a DrawRecord so that anyone can replay it,
+		/// with the dice of an Athenian draw.
+		/// A second stage is drawn among the jurors who accepted their summons in its first stage,
+		/// once that one is closed.
+		/// The weight charged is that of a draw with every tribe full, see draw_jurors_weight,
+		/// and the part the actual candidates did not use is refunded.
+		/// The draw is transactional: the candidates imported from a first stage are rolled back if it fails.
+		#[pallet::weight(draw_jurors_weight::<T>())]
+		#[transactional]
+		pub fn draw_jurors(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResultWithPostInfo {
+			let _who = ensure_signed(origin)?;
+
+			let mut jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			match jury_call.status {
+				JuryCallStatus::Registering | JuryCallStatus::ReadyToDraw => {
+					ensure!(time_now >= jury_call.draw_after, Error::<T>::DrawTooEarly)
+				},
+				JuryCallStatus::Drawn | JuryCallStatus::Closed => Err(Error::<T>::AlreadyDrawn)?,
+				JuryCallStatus::Cancelled => Err(Error::<T>::InvalidStatusTransition)?,
+			}
+
+			// Check the runtime still implements the algorithm the Jury Call was opened with
+			ensure!(sortition::is_supported(jury_call.algorithm), Error::<T>::UnsupportedAlgorithm);
+
+			// Check the first stage is over, its accepted jurors are the candidates
+			if let Some(first_stage_id) = jury_call.first_stage {
+				let first_stage = Self::jury_call(first_stage_id).ok_or(Error::<T>::FirstStageNotFound)?;
+				ensure!(first_stage.status == JuryCallStatus::Closed, Error::<T>::FirstStageNotClosed);
+			}
+
+			// Nobody can predict the secrets of the others, so a single honest revealer makes the seed unpredictable.
+			// The reveals are not made again after a void, the number of voids tells the draws apart.
+			let voids = Self::draw_voids(jury_call_id);
+			let (seed, block) = if jury_call.commit_reveal {
+				let entropy = Self::revealed_entropy(jury_call_id).ok_or(Error::<T>::NoReveals)?;
+				(T::Hashing::hash_of(&(DRAW_SUBJECT, jury_call_id, voids, entropy)), <frame_system::Pallet<T>>::block_number())
+			} else {
+				T::Randomness::random(&(DRAW_SUBJECT, jury_call_id, voids).encode())
+			};
+
+			let imported = match jury_call.first_stage {
+				Some(first_stage_id) => Self::import_pool(jury_call_id, first_stage_id, jury_call.tribes.len()),
+				None => 0,
+			};
+
+			// Rank the candidates of every tribe before writing anything else
+			let mut inputs = Vec::with_capacity(jury_call.tribes.len());
+			let mut exclusions = Vec::with_capacity(jury_call.tribes.len());
+			for index in 0..jury_call.tribes.len() {
+				// Judgements may have changed since registration, and bonds since a voided draw
+				let (candidates, excluded): (Vec<AccountOf<T>>, Vec<AccountOf<T>>) = Self::candidates(jury_call_id, index as TribeIndex)
+					.into_iter()
+					.partition(|who| {
+						jury_call.identity.map_or(true, |requirement| requirement.is_met::<T>(who)) &&
+							(!jury_call.commit_reveal || <Reveals<T>>::contains_key(jury_call_id, who)) &&
+							(jury_call.candidate_bond.is_zero() || <Bonds<T>>::contains_key(jury_call_id, who))
+					});
+				inputs.push(candidates);
+				exclusions.push(excluded);
+			}
+			let quotas = Self::quotas(jury_call_id).unwrap_or_default().into_inner();
+			let (rankings, dice) = Self::rank_tribes(jury_call_id, &jury_call, &seed, &inputs, &quotas)
+				.ok_or(Error::<T>::QuotasInfeasible)?;
+			// In an Athenian draw, a tribe gets a juror for every white die
+			let white = dice.iter().filter(|die| **die).count();
+			let dice: BoundedVec<bool, T::MaxCandidatesPerTribe> = dice
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyCandidates)?;
+
+			// The weight actually used depends on the number of candidates read
+			let tribe_sizes: Vec<usize> = inputs.iter().zip(exclusions.iter()).map(|(ranked, excluded)| ranked.len() + excluded.len()).collect();
+			let actual_weight = draw_weight::<T>(
+				jury_call.tribes.len() as Weight,
+				tribe_sizes.iter().sum::<usize>().max(imported) as Weight,
+				tribe_sizes.iter().copied().max().unwrap_or(0) as Weight,
+				jury_call.draw_mode == DrawMode::Stratified,
+			);
+
+			let mut draws = Vec::with_capacity(jury_call.tribes.len());
+			for (index, ((tribe, ranking), excluded)) in jury_call.tribes.iter().zip(rankings).zip(exclusions).enumerate() {
+				let tribe_index = index as TribeIndex;
+				let seats = match jury_call.draw_mode {
+					DrawMode::Athenian => white,
+					_ => ranking.len().min(tribe.seats as usize),
+				};
+				let jurors: BoundedVec<AccountOf<T>, T::MaxCandidatesPerTribe> = ranking[..seats]
+					.to_vec()
+					.try_into()
+					.map_err(|_| Error::<T>::TooManyCandidates)?;
+				let ranking: BoundedVec<AccountOf<T>, T::MaxCandidatesPerTribe> = ranking
+					.try_into()
+					.map_err(|_| Error::<T>::TooManyCandidates)?;
+				draws.push((tribe_index, seats, jurors, ranking, excluded));
+			}
+
+			// Registration ended at registration_closes, nobody may have called close_registration yet
+			if jury_call.status == JuryCallStatus::Registering {
+				Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::ReadyToDraw)?;
+			}
+
+			<DrawRecords<T>>::insert(jury_call_id, DrawRecord::<T> {
+				seed,
+				block,
+				drawn_at: time_now,
+				dice,
+				algorithm_version: jury_call.algorithm,
+				candidates_hash: T::Hashing::hash_of(&inputs),
+			});
+
+			for (tribe_index, seats, jurors, ranking, excluded) in draws {
+				for who in excluded {
+					// Withholding a committed secret could bias the draw
+					let withheld = jury_call.commit_reveal &&
+						<Commitments<T>>::contains_key(jury_call_id, &who) &&
+						!<Reveals<T>>::contains_key(jury_call_id, &who);
+					if withheld {
+						Self::slash_bond(jury_call_id, &who);
+					} else {
+						Self::release_bond(jury_call_id, &who);
+					}
+					Self::deposit_event(Event::CandidateExcluded(jury_call_id, tribe_index, who));
+				}
+				let summons = Summons {
+					tribe: tribe_index,
+					deadline: time_now.saturating_add(T::SummonsPeriod::get()),
+					status: SummonsStatus::Pending,
+				};
+				for juror in jurors.iter() {
+					<JurorSummons<T>>::insert(jury_call_id, juror, summons.clone());
+				}
+				<SelectedJurors<T>>::insert(jury_call_id, tribe_index, jurors.clone());
+				<Rankings<T>>::insert(jury_call_id, tribe_index, ranking);
+				<ReserveCursor<T>>::insert(jury_call_id, tribe_index, seats as u32);
+
+				Self::deposit_event(Event::JurorsSelected(jury_call_id, tribe_index, jurors.into_inner()));
+			}
+
+			Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::Drawn)?;
+			<JuryCalls<T>>::insert(jury_call_id, jury_call);
+
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Closes a Jury Call whose jurors have been drawn, releases the bonds left
+		/// and refunds the rest of the compensation budget to the owner.
+		/// Only the owner can close it, once the challenge period is over and every challenge resolved.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,3) + clear_weight::<T>(2, 0, 0))]
+		pub fn close_jury_call(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+
+			Self::ensure_draw_final(jury_call_id)?;
+
+			Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::Closed)?;
+			Self::refund_escrow(jury_call_id, &jury_call.owner)?;
+			Self::release_bonds(jury_call_id);
+			Self::release_first_stage(&jury_call);
+			<JuryCalls<T>>::insert(jury_call_id, jury_call);
+
+			Ok(())
+		}
+
+		/// Cancels a Jury Call before its draw, releases its candidates and their bonds
+		/// and refunds the compensation budget to the owner.
+		/// Only the owner or root can cancel a Jury Call, giving a reason of at most MaxReasonLen bytes.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,5) + clear_weight::<T>(7, 1, 0))]
+		pub fn cancel_jury_call(origin: OriginFor<T>, jury_call_id: JuryCallID, reason: Vec<u8>) -> DispatchResult {
+			let maybe_who = match ensure_root(origin.clone()) {
+				Ok(()) => None,
+				Err(_) => Some(ensure_signed(origin)?),
+			};
+			ensure!(reason.len() <= T::MaxReasonLen::get() as usize, Error::<T>::ReasonTooLong);
+
+			let mut jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			if let Some(who) = maybe_who {
+				ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+			}
+
+			Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::Cancelled)?;
+			Self::refund_escrow(jury_call_id, &jury_call.owner)?;
+
+			// The candidates are free again
+			let candidates = max_candidates::<T>();
+			for index in 0..jury_call.tribes.len() {
+				let tribe_index = index as TribeIndex;
+				<TribeCandidates<T>>::remove_prefix((jury_call_id, tribe_index), Some(T::MaxCandidatesPerTribe::get()));
+				<CandidateCount<T>>::remove(jury_call_id, tribe_index);
+			}
+			<Candidacies<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<CandidateAttributes<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Commitments<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Reveals<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Quotas<T>>::remove(jury_call_id);
+			Self::release_bonds(jury_call_id);
+			Self::release_first_stage(&jury_call);
+			<JuryCalls<T>>::insert(jury_call_id, jury_call);
+
+			Self::deposit_event(Event::JuryCallCancelled(jury_call_id, reason));
+
+			Ok(())
+		}
+
+		/// Removes every trace of a closed or cancelled Jury Call from storage
+		/// and returns the deposit to the owner. Only the owner can remove a Jury Call,
+		/// and a first stage only once its second stages are closed or cancelled.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,7) + clear_weight::<T>(6, 4, 2))]
+		pub fn remove_jury_call(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+			ensure!(
+				matches!(jury_call.status, JuryCallStatus::Closed | JuryCallStatus::Cancelled),
+				Error::<T>::JuryCallNotFinished
+			);
+			// Check no second stage still needs the summons of this Jury Call to draw
+			ensure!(Self::second_stage_count(jury_call_id) == 0, Error::<T>::FirstStageInUse);
+
+			// Escrows and bonds were settled when the Jury Call was closed or cancelled.
+			// No map holds more than an entry per candidate, tribe or challenge.
+			let (tribes, candidates) = (T::MaxTribes::get(), max_candidates::<T>());
+			for index in 0..jury_call.tribes.len() {
+				<TribeCandidates<T>>::remove_prefix((jury_call_id, index as TribeIndex), Some(T::MaxCandidatesPerTribe::get()));
+			}
+			<CandidateCount<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<Candidacies<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<CandidateAttributes<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Commitments<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Reveals<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Quotas<T>>::remove(jury_call_id);
+			<SelectedJurors<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<Rankings<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<ReserveCursor<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<DrawRecords<T>>::remove(jury_call_id);
+			<JurorSummons<T>>::remove_prefix(jury_call_id, Some(candidates));
+			<Challenges<T>>::remove_prefix(jury_call_id, Some(max_challenges::<T>()));
+			<ChallengedReasons<T>>::remove_prefix(jury_call_id, Some(max_challenges::<T>()));
+			<ChallengeCount<T>>::remove(jury_call_id);
+			<DrawVoids<T>>::remove(jury_call_id);
+			<SecondStageCount<T>>::remove(jury_call_id);
+			<JuryCalls<T>>::remove(jury_call_id);
+
+			T::Currency::unreserve(&jury_call.owner, jury_call.deposit);
+
+			Self::deposit_event(Event::JuryCallRemoved(jury_call_id, jury_call.owner, jury_call.deposit));
+
+			Ok(())
+		}
+
+		/// Accepts the summons received by the signer after the draw.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn accept_summons(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut summons = Self::pending_summons(jury_call_id, &who)?;
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(time_now < summons.deadline, Error::<T>::SummonsExpired);
+
+			summons.status = SummonsStatus::Accepted;
+			<JurorSummons<T>>::insert(jury_call_id, &who, summons);
+
+			Self::deposit_event(Event::SummonsAccepted(jury_call_id, who));
+
+			Ok(())
+		}
+
+		/// Declines the summons received by the signer after the draw, which releases their bond.
+		/// The next reserve of the tribe, if any, is summoned instead.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,5))]
+		pub fn decline_summons(origin: OriginFor<T>, jury_call_id: JuryCallID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut summons = Self::pending_summons(jury_call_id, &who)?;
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+
+			summons.status = SummonsStatus::Declined;
+			<JurorSummons<T>>::insert(jury_call_id, &who, summons.clone());
+			Self::release_bond(jury_call_id, &who);
+			Self::deposit_event(Event::SummonsDeclined(jury_call_id, who.clone()));
+
+			Self::replace_juror(jury_call_id, summons.tribe, &who, time_now)
+		}
+
+		/// Replaces a juror who did not answer their summons in time by the next reserve of the tribe.
+		/// Their bond goes to the owner. Anyone can trigger the replacement.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4,5))]
+		pub fn replace_unresponsive_juror(origin: OriginFor<T>, jury_call_id: JuryCallID, juror: T::AccountId) -> DispatchResult {
+			let _who = ensure_signed(origin)?;
+
+			let mut summons = Self::pending_summons(jury_call_id, &juror)?;
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(time_now >= summons.deadline, Error::<T>::SummonsNotExpired);
+
+			summons.status = SummonsStatus::TimedOut;
+			<JurorSummons<T>>::insert(jury_call_id, &juror, summons.clone());
+			Self::slash_bond(jury_call_id, &juror);
+			Self::deposit_event(Event::SummonsTimedOut(jury_call_id, juror.clone()));
+
+			Self::replace_juror(jury_call_id, summons.tribe, &juror, time_now)
+		}
+
+		/// Reports that a juror who accepted their summons did not attend. Their bond goes to the owner.
+		/// Only the owner can report an absence, once the draw can no longer be challenged and before closing the Jury Call.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,3))]
+		pub fn report_absence(origin: OriginFor<T>, jury_call_id: JuryCallID, juror: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+			ensure!(jury_call.status == JuryCallStatus::Drawn, Error::<T>::NotDrawn);
+			Self::ensure_draw_final(jury_call_id)?;
+
+			let mut summons = Self::summons(jury_call_id, &juror).ok_or(Error::<T>::NotSummoned)?;
+			ensure!(summons.status == SummonsStatus::Accepted, Error::<T>::SummonsNotAccepted);
+
+			summons.status = SummonsStatus::Absent;
+			<JurorSummons<T>>::insert(jury_call_id, &juror, summons);
+			Self::slash_bond(jury_call_id, &juror);
+
+			Self::deposit_event(Event::JurorAbsent(jury_call_id, juror));
+
+			Ok(())
+		}
+
+		/// Confirms that a juror who accepted their summons completed their service.
+		/// The juror is paid their compensation out of the escrow and gets their bond back.
+		/// Only the owner can confirm a service, once the draw can no longer be challenged and before closing the Jury Call.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,4))]
+		pub fn confirm_service(origin: OriginFor<T>, jury_call_id: JuryCallID, juror: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+			ensure!(jury_call.status == JuryCallStatus::Drawn, Error::<T>::NotDrawn);
+			Self::ensure_draw_final(jury_call_id)?;
+
+			let mut summons = Self::summons(jury_call_id, &juror).ok_or(Error::<T>::NotSummoned)?;
+			ensure!(summons.status == SummonsStatus::Accepted, Error::<T>::SummonsNotAccepted);
+
+			// Every seat is funded, but a replaced seat may have been served twice
+			let escrow = Self::escrow(jury_call_id).unwrap_or_else(Zero::zero);
+			let payment = jury_call.juror_compensation.min(escrow);
+			T::Currency::transfer(&Self::account_id(), &juror, payment, ExistenceRequirement::AllowDeath)?;
+
+			summons.status = SummonsStatus::Served;
+			<JurorSummons<T>>::insert(jury_call_id, &juror, summons);
+			if escrow > payment {
+				<Escrows<T>>::insert(jury_call_id, escrow - payment);
+			} else {
+				<Escrows<T>>::remove(jury_call_id);
+			}
+			Self::release_bond(jury_call_id, &juror);
+
+			if !payment.is_zero() {
+				Self::deposit_event(Event::JurorPaid(jury_call_id, juror, payment));
+			}
+
+			Ok(())
+		}
+
+		/// Commits the signer, a candidate of a commit-reveal Jury Call, to a secret.
+		/// The commitment is the hash of (account, secret) and must be made while registration is open.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
+		pub fn commit_secret(origin: OriginFor<T>, jury_call_id: JuryCallID, commitment: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.commit_reveal, Error::<T>::NotCommitReveal);
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(jury_call.status == JuryCallStatus::Registering, Error::<T>::RegistrationClosed);
+			ensure!(time_now < jury_call.registration_closes, Error::<T>::RegistrationClosed);
+			ensure!(<Candidacies<T>>::contains_key(jury_call_id, &who), Error::<T>::NotACandidate);
+			ensure!(!<Commitments<T>>::contains_key(jury_call_id, &who), Error::<T>::AlreadyCommitted);
+
+			<Commitments<T>>::insert(jury_call_id, &who, commitment);
+
+			Self::deposit_event(Event::SecretCommitted(jury_call_id, who));
+
+			Ok(())
+		}
+
+		/// Reveals the secret the signer committed to, from registration_closes until draw_after.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
+		pub fn reveal_secret(origin: OriginFor<T>, jury_call_id: JuryCallID, secret: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.commit_reveal, Error::<T>::NotCommitReveal);
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(
+				matches!(jury_call.status, JuryCallStatus::Registering | JuryCallStatus::ReadyToDraw),
+				Error::<T>::RevealNotOpen
+			);
+			ensure!(time_now >= jury_call.registration_closes, Error::<T>::RevealNotOpen);
+			ensure!(time_now < jury_call.draw_after, Error::<T>::RevealNotOpen);
+
+			let commitment = Self::commitment(jury_call_id, &who).ok_or(Error::<T>::NoCommitment)?;
+			ensure!(!<Reveals<T>>::contains_key(jury_call_id, &who), Error::<T>::AlreadyRevealed);
+			ensure!(T::Hashing::hash_of(&(&who, secret)) == commitment, Error::<T>::InvalidReveal);
+
+			<Reveals<T>>::insert(jury_call_id, &who, secret);
+
+			Self::deposit_event(Event::SecretRevealed(jury_call_id, who));
+
+			Ok(())
+		}
+
+		/// Challenges the draw of a Jury Call during the challenge period, reserving the challenge bond
+		/// from the signer. Anyone can challenge a draw, once per Jury Call, on a reason no unresolved
+		/// challenge contests yet: the draw itself or a juror, so there is room for every reason.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6,4))]
+		pub fn submit_challenge(origin: OriginFor<T>, jury_call_id: JuryCallID, reason: ChallengeReason<T::AccountId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.status == JuryCallStatus::Drawn, Error::<T>::NotDrawn);
+
+			let record = Self::draw_record(jury_call_id).ok_or(Error::<T>::NotDrawn)?;
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(time_now < record.drawn_at.saturating_add(T::ChallengePeriod::get()), Error::<T>::ChallengePeriodOver);
+			ensure!(!<Challenges<T>>::contains_key(jury_call_id, &who), Error::<T>::AlreadyChallenged);
+			ensure!(!<ChallengedReasons<T>>::contains_key(jury_call_id, &reason), Error::<T>::ReasonAlreadyChallenged);
+			let challenges = Self::challenge_count(jury_call_id);
+			if let ChallengeReason::IneligibleJuror(juror) = &reason {
+				ensure!(<JurorSummons<T>>::contains_key(jury_call_id, juror), Error::<T>::NotSummoned);
+			}
+
+			let bond = T::ChallengeBond::get();
+			T::Currency::reserve(&who, bond).map_err(|_| Error::<T>::InsufficientChallengeBond)?;
+			<ChallengedReasons<T>>::insert(jury_call_id, &reason, &who);
+			<Challenges<T>>::insert(jury_call_id, &who, Challenge::<T> { reason, bond });
+			<ChallengeCount<T>>::insert(jury_call_id, challenges.saturating_add(1));
+
+			Self::deposit_event(Event::ChallengeSubmitted(jury_call_id, who));
+
+			Ok(())
+		}
+
+		/// Resolves a challenge. Upheld, the challenger gets their bond back and the contested
+		/// juror is replaced, or the whole draw voided; rejected, their bond goes to the owner.
+		/// Only root or the ChallengeOrigin can resolve a challenge.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7,11) + clear_weight::<T>(1, 3, 3))]
+		pub fn resolve_challenge(origin: OriginFor<T>, jury_call_id: JuryCallID, challenger: T::AccountId, upheld: bool) -> DispatchResult {
+			T::ChallengeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			let mut jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			let challenge = Self::challenge(jury_call_id, &challenger).ok_or(Error::<T>::ChallengeNotFound)?;
+
+			if !upheld {
+				// What cannot be moved to the owner is burnt
+				<Challenges<T>>::remove(jury_call_id, &challenger);
+				<ChallengedReasons<T>>::remove(jury_call_id, &challenge.reason);
+				<ChallengeCount<T>>::mutate(jury_call_id, |count| *count = count.saturating_sub(1));
+				let remaining = T::Currency::repatriate_reserved(&challenger, &jury_call.owner, challenge.bond, BalanceStatus::Free)
+					.unwrap_or(challenge.bond);
+				let _ = T::Currency::slash_reserved(&challenger, remaining);
+				Self::deposit_event(Event::ChallengeRejected(jury_call_id, challenger, challenge.bond));
+				return Ok(());
+			}
+
+			match &challenge.reason {
+				ChallengeReason::IneligibleJuror(juror) => {
+					<Challenges<T>>::remove(jury_call_id, &challenger);
+					<ChallengedReasons<T>>::remove(jury_call_id, &challenge.reason);
+					<ChallengeCount<T>>::mutate(jury_call_id, |count| *count = count.saturating_sub(1));
+					Self::void_juror(jury_call_id, juror)?;
+				},
+				ChallengeReason::WrongSeed => {
+					Self::set_status(jury_call_id, &mut jury_call, JuryCallStatus::ReadyToDraw)?;
+					<Challenges<T>>::remove(jury_call_id, &challenger);
+					Self::void_draw(jury_call_id);
+					<JuryCalls<T>>::insert(jury_call_id, jury_call);
+				},
+			}
+			T::Currency::unreserve(&challenger, challenge.bond);
+
+			Self::deposit_event(Event::ChallengeUpheld(jury_call_id, challenger));
+
+			Ok(())
+		}
+
+		/// Sets the quotas every tribe's panel must meet in a stratified Jury Call, replacing the previous ones.
+		/// Only the owner can set them, until the draw.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		pub fn set_quotas(origin: OriginFor<T>, jury_call_id: JuryCallID, quotas: Vec<Quota>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.owner == who, Error::<T>::NotOwner);
+			ensure!(jury_call.draw_mode == DrawMode::Stratified, Error::<T>::NotStratified);
+			ensure!(
+				matches!(jury_call.status, JuryCallStatus::Registering | JuryCallStatus::ReadyToDraw),
+				Error::<T>::QuotasFrozen
+			);
+
+			// Check the number of quotas before comparing them
+			ensure!(quotas.len() <= T::MaxQuotas::get() as usize, Error::<T>::TooManyQuotas);
+
+			// Check every quota is consistent and counts its own attribute value
+			ensure!(quotas.iter().all(|quota| quota.min <= quota.max), Error::<T>::InvalidQuota);
+			for (index, quota) in quotas.iter().enumerate() {
+				ensure!(
+					!quotas[..index].iter().any(|other| other.key == quota.key && other.value == quota.value),
+					Error::<T>::DuplicateQuota
+				);
+			}
+			let quotas: BoundedVec<Quota, T::MaxQuotas> = quotas.try_into().map_err(|_| Error::<T>::TooManyQuotas)?;
+
+			<Quotas<T>>::insert(jury_call_id, quotas);
+
+			Self::deposit_event(Event::QuotasSet(jury_call_id));
+
+			Ok(())
+		}
+
+		/// Declares the attribute values of the signer, a candidate of a stratified Jury Call,
+		/// replacing the previous ones. Attributes can change as long as registration is open.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		pub fn set_attributes(
+			origin: OriginFor<T>,
+			jury_call_id: JuryCallID,
+			attributes: Vec<(AttributeKey, AttributeValue)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.draw_mode == DrawMode::Stratified, Error::<T>::NotStratified);
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			ensure!(jury_call.status == JuryCallStatus::Registering, Error::<T>::RegistrationClosed);
+			ensure!(time_now < jury_call.registration_closes, Error::<T>::RegistrationClosed);
+			ensure!(<Candidacies<T>>::contains_key(jury_call_id, &who), Error::<T>::NotACandidate);
+
+			// Check the number of attributes before comparing them
+			ensure!(attributes.len() <= T::MaxAttributes::get() as usize, Error::<T>::TooManyAttributes);
+
+			// Check every attribute has a single value
+			for (index, (key, _)) in attributes.iter().enumerate() {
+				ensure!(!attributes[..index].iter().any(|(other, _)| other == key), Error::<T>::DuplicateAttribute);
+			}
+			let attributes: BoundedVec<(AttributeKey, AttributeValue), T::MaxAttributes> = attributes
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyAttributes)?;
+
+			<CandidateAttributes<T>>::insert(jury_call_id, &who, attributes);
+
+			Self::deposit_event(Event::AttributesSet(jury_call_id, who));
+
+			Ok(())
+		}
+
 		// #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		// pub fn test_time(origin: OriginFor<T>,  start_after: u64) -> DispatchResult {
 		// 	// Check that the extrinsic was signed and get the signer.
@@ -219,6 +1636,422 @@ pub mod pallet {
 
 	}
 
+	impl<T: Config> Pallet<T> {
+		/// Returns the candidates of a tribe, sorted so that the order does not depend on storage
+		pub fn candidates(jury_call_id: JuryCallID, tribe_index: TribeIndex) -> Vec<AccountOf<T>> {
+			let mut candidates: Vec<AccountOf<T>> = <TribeCandidates<T>>::iter_prefix((jury_call_id, tribe_index))
+				.map(|(who, _)| who)
+				.collect();
+			candidates.sort();
+			candidates
+		}
+
+		/// Recomputes the ranking of every tribe of a drawn Jury Call from its draw record.
+		/// The candidates given to the draw are the ones ranked, in account order, and must match the recorded hash.
+		/// Returns None when the draw cannot be replayed.
+		pub fn replay_draw(jury_call_id: JuryCallID) -> Option<Vec<Vec<AccountOf<T>>>> {
+			Self::replay(jury_call_id).map(|(rankings, _)| rankings)
+		}
+
+		/// Returns true if replaying the draw of a Jury Call gives the stored rankings and dice
+		pub fn verify_draw(jury_call_id: JuryCallID) -> bool {
+			let record = match Self::draw_record(jury_call_id) {
+				Some(record) => record,
+				None => return false,
+			};
+			match Self::replay(jury_call_id) {
+				Some((rankings, dice)) => dice == record.dice.into_inner() &&
+					rankings.into_iter().enumerate().all(|(index, ranking)| {
+						Self::ranking(jury_call_id, index as TribeIndex).map(|stored| stored.into_inner()) == Some(ranking)
+					}),
+				None => false,
+			}
+		}
+
+		/// Replays the draw of a Jury Call, giving the rankings of its tribes and the dice of an Athenian draw
+		fn replay(jury_call_id: JuryCallID) -> Option<(Vec<Vec<AccountOf<T>>>, Vec<bool>)> {
+			let jury_call = Self::jury_call(jury_call_id)?;
+			let record = Self::draw_record(jury_call_id)?;
+			if record.algorithm_version != jury_call.algorithm { return None; }
+
+			let inputs: Vec<Vec<AccountOf<T>>> = (0..jury_call.tribes.len())
+				.map(|index| {
+					let mut candidates = Self::ranking(jury_call_id, index as TribeIndex).unwrap_or_default().into_inner();
+					candidates.sort();
+					candidates
+				})
+				.collect();
+			if T::Hashing::hash_of(&inputs) != record.candidates_hash { return None; }
+
+			let quotas = Self::quotas(jury_call_id).unwrap_or_default().into_inner();
+			Self::rank_tribes(jury_call_id, &jury_call, &record.seed, &inputs, &quotas)
+		}
+
+		/// Returns the alternates of a tribe, in the order drawn, whether summoned or not
+		pub fn alternates(jury_call_id: JuryCallID, tribe_index: TribeIndex) -> Vec<AccountOf<T>> {
+			let ranking = Self::ranking(jury_call_id, tribe_index).unwrap_or_default();
+			let jury_call = match Self::jury_call(jury_call_id) {
+				Some(jury_call) => jury_call,
+				None => return Vec::new(),
+			};
+			// Skip as many jurors as the draw seated, an Athenian draw seats one per white die
+			let jurors = match jury_call.draw_mode {
+				DrawMode::Athenian => Self::draw_record(jury_call_id)
+					.map(|record| record.dice.iter().filter(|die| **die).count())
+					.unwrap_or_default(),
+				_ => jury_call.tribes
+					.get(tribe_index as usize)
+					.map(|tribe| ranking.len().min(tribe.seats as usize))
+					.unwrap_or_default(),
+			};
+			ranking.into_inner().into_iter().skip(jurors).collect()
+		}
+
+		/// Returns the alternates of a tribe who have not been summoned yet, in order
+		pub fn reserves(jury_call_id: JuryCallID, tribe_index: TribeIndex) -> Vec<AccountOf<T>> {
+			let cursor = Self::reserve_cursor(jury_call_id, tribe_index);
+			let ranking = Self::ranking(jury_call_id, tribe_index).unwrap_or_default();
+			ranking.into_inner().into_iter().skip(cursor as usize).collect()
+		}
+
+		/// Returns the rank (starting at 1) drawn for a candidate of a tribe
+		pub fn rank_of(jury_call_id: JuryCallID, tribe_index: TribeIndex, who: &AccountOf<T>) -> Option<u32> {
+			Self::ranking(jury_call_id, tribe_index)?
+				.iter()
+				.position(|candidate| candidate == who)
+				.map(|position| position as u32 + 1)
+		}
+
+		/// Combines the secrets revealed in a Jury Call by hashing them in account order, whatever the order of the reveals.
+		/// Unlike a XOR, equal secrets do not cancel out. Returns None when nobody revealed their secret.
+		pub fn revealed_entropy(jury_call_id: JuryCallID) -> Option<T::Hash> {
+			let mut reveals: Vec<(AccountOf<T>, T::Hash)> = <Reveals<T>>::iter_prefix(jury_call_id).collect();
+			if reveals.is_empty() { return None; }
+			reveals.sort_by(|a, b| a.0.cmp(&b.0));
+			Some(T::Hashing::hash_of(&reveals))
+		}
+
+		/// The account holding the compensation escrows
+		pub fn account_id() -> AccountOf<T> {
+			T::PalletId::get().into_account()
+		}
+
+		/// Pays what is left of the compensation budget of a Jury Call back to its owner
+		fn refund_escrow(jury_call_id: JuryCallID, owner: &AccountOf<T>) -> DispatchResult {
+			if let Some(escrow) = Self::escrow(jury_call_id) {
+				T::Currency::transfer(&Self::account_id(), owner, escrow, ExistenceRequirement::AllowDeath)?;
+				<Escrows<T>>::remove(jury_call_id);
+				Self::deposit_event(Event::CompensationRefunded(jury_call_id, owner.clone(), escrow));
+			}
+			Ok(())
+		}
+
+		/// Unreserves the bond of a candidate, if they still have one
+		fn release_bond(jury_call_id: JuryCallID, who: &AccountOf<T>) {
+			if let Some(bond) = <Bonds<T>>::take(jury_call_id, who) {
+				T::Currency::unreserve(who, bond);
+			}
+		}
+
+		/// Unreserves every bond still held for a Jury Call
+		fn release_bonds(jury_call_id: JuryCallID) {
+			for (who, bond) in <Bonds<T>>::drain_prefix(jury_call_id) {
+				T::Currency::unreserve(&who, bond);
+			}
+		}
+
+		/// Moves the bond of a juror who failed to serve to the owner of the Jury Call.
+		/// What cannot be moved to the owner is burnt.
+		fn slash_bond(jury_call_id: JuryCallID, who: &AccountOf<T>) {
+			let bond = match <Bonds<T>>::take(jury_call_id, who) {
+				Some(bond) => bond,
+				None => return,
+			};
+			let remaining = match Self::jury_call(jury_call_id) {
+				Some(jury_call) => T::Currency::repatriate_reserved(who, &jury_call.owner, bond, BalanceStatus::Free)
+					.unwrap_or(bond),
+				None => bond,
+			};
+			let _ = T::Currency::slash_reserved(who, remaining);
+			Self::deposit_event(Event::BondSlashed(jury_call_id, who.clone(), bond));
+		}
+
+		/// Moves a Jury Call to a new status if the transition is legal.
+		/// The caller is responsible for storing the Jury Call afterwards.
+		fn set_status(jury_call_id: JuryCallID, jury_call: &mut JuryCall<T>, status: JuryCallStatus) -> DispatchResult {
+			ensure!(jury_call.status.can_become(status), Error::<T>::InvalidStatusTransition);
+			let previous = sp_std::mem::replace(&mut jury_call.status, status);
+			Self::deposit_event(Event::StatusChanged(jury_call_id, previous, status));
+			Ok(())
+		}
+
+		/// Checks the draw of a Jury Call can no longer be challenged nor voided:
+		/// the challenge period is over and every challenge is resolved
+		fn ensure_draw_final(jury_call_id: JuryCallID) -> DispatchResult {
+			if let Some(record) = Self::draw_record(jury_call_id) {
+				let time_now: u64 = T::TimeProvider::now().as_secs();
+				ensure!(time_now >= record.drawn_at.saturating_add(T::ChallengePeriod::get()), Error::<T>::ChallengePeriodOpen);
+				ensure!(Self::challenge_count(jury_call_id) == 0, Error::<T>::ChallengePending);
+			}
+			Ok(())
+		}
+
+		/// Returns the summons of a juror of a drawn Jury Call, if it is still waiting for an answer
+		fn pending_summons(jury_call_id: JuryCallID, who: &AccountOf<T>) -> Result<Summons, DispatchError> {
+			let jury_call = Self::jury_call(jury_call_id).ok_or(Error::<T>::JuryCallNotFound)?;
+			ensure!(jury_call.status == JuryCallStatus::Drawn, Error::<T>::NotDrawn);
+
+			let summons = Self::summons(jury_call_id, who).ok_or(Error::<T>::NotSummoned)?;
+			ensure!(summons.status == SummonsStatus::Pending, Error::<T>::SummonsNotPending);
+			Ok(summons)
+		}
+
+		/// Gives the seat of a juror to the next reserve of the tribe.
+		/// The seat stays empty when the tribe has no reserve left.
+		fn replace_juror(jury_call_id: JuryCallID, tribe_index: TribeIndex, juror: &AccountOf<T>, time_now: u64) -> DispatchResult {
+			let mut jurors = Self::selected_jurors(jury_call_id, tribe_index).unwrap_or_default().into_inner();
+			let cursor = Self::reserve_cursor(jury_call_id, tribe_index);
+			let ranking = Self::ranking(jury_call_id, tribe_index).unwrap_or_default();
+
+			let seat = jurors.iter().position(|selected| selected == juror);
+			let replacement = match (seat, ranking.get(cursor as usize)) {
+				(Some(seat), Some(replacement)) => {
+					jurors[seat] = replacement.clone();
+					Some(replacement.clone())
+				},
+				(Some(seat), None) => {
+					jurors.remove(seat);
+					None
+				},
+				(None, _) => None,
+			};
+			let jurors: BoundedVec<AccountOf<T>, T::MaxCandidatesPerTribe> = jurors
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyCandidates)?;
+			<SelectedJurors<T>>::insert(jury_call_id, tribe_index, jurors);
+
+			if let Some(replacement) = replacement {
+				<JurorSummons<T>>::insert(jury_call_id, &replacement, Summons {
+					tribe: tribe_index,
+					deadline: time_now.saturating_add(T::SummonsPeriod::get()),
+					status: SummonsStatus::Pending,
+				});
+				<ReserveCursor<T>>::insert(jury_call_id, tribe_index, cursor.saturating_add(1));
+				Self::deposit_event(Event::JurorReplaced(jury_call_id, tribe_index, juror.clone(), replacement));
+			}
+
+			Ok(())
+		}
+
+		/// Takes the seat of a juror found ineligible and gives it to the next reserve of the tribe.
+		/// The juror gets their bond back. Nothing happens if they no longer hold a seat.
+		fn void_juror(jury_call_id: JuryCallID, juror: &AccountOf<T>) -> DispatchResult {
+			let mut summons = match Self::summons(jury_call_id, juror) {
+				Some(summons) => summons,
+				None => return Ok(()),
+			};
+			let seated = Self::selected_jurors(jury_call_id, summons.tribe).map_or(false, |jurors| jurors.contains(juror));
+			if !seated { return Ok(()); }
+
+			summons.status = SummonsStatus::Voided;
+			<JurorSummons<T>>::insert(jury_call_id, juror, summons.clone());
+			Self::release_bond(jury_call_id, juror);
+			Self::deposit_event(Event::JurorVoided(jury_call_id, summons.tribe, juror.clone()));
+
+			let time_now: u64 = T::TimeProvider::now().as_secs();
+			Self::replace_juror(jury_call_id, summons.tribe, juror, time_now)
+		}
+
+		/// Lets the first stage of a closed or cancelled second stage be removed once no other second stage needs it
+		fn release_first_stage(jury_call: &JuryCall<T>) {
+			if let Some(first_stage_id) = jury_call.first_stage {
+				<SecondStageCount<T>>::mutate(first_stage_id, |count| *count = count.saturating_sub(1));
+			}
+		}
+
+		/// Registers the jurors who accepted their summons in the first stage as the candidates of a second stage,
+		/// in the tribe they were drawn in. Importing the pool again gives the same candidates.
+		/// Returns the number of summons read.
+		fn import_pool(jury_call_id: JuryCallID, first_stage_id: JuryCallID, tribes: usize) -> usize {
+			let mut counts: Vec<Candidates> = sp_std::vec![0; tribes];
+			let mut read = 0;
+			for (who, summons) in <JurorSummons<T>>::iter_prefix(first_stage_id) {
+				read += 1;
+				if !matches!(summons.status, SummonsStatus::Accepted | SummonsStatus::Served) { continue; }
+				let tribe_index = summons.tribe;
+				let count = match counts.get_mut(tribe_index as usize) {
+					Some(count) if (*count as u32) < T::MaxCandidatesPerTribe::get() => count,
+					_ => continue,
+				};
+				*count = count.saturating_add(1);
+				let weight = T::WeightSource::weight_of(jury_call_id, &who);
+				<TribeCandidates<T>>::insert((jury_call_id, tribe_index), &who, weight);
+				<Candidacies<T>>::insert(jury_call_id, &who, tribe_index);
+			}
+			for (index, count) in counts.into_iter().enumerate() {
+				<CandidateCount<T>>::insert(jury_call_id, index as TribeIndex, count);
+			}
+			Self::deposit_event(Event::CandidatePoolImported(jury_call_id, first_stage_id));
+			read
+		}
+
+		/// Forgets the outcome of the draw of a Jury Call so that the lot can be drawn again with a new seed.
+		/// The candidates keep their bond for the new draw and the other challenges are dropped.
+		/// The caller is responsible for moving the Jury Call back to ReadyToDraw.
+		fn void_draw(jury_call_id: JuryCallID) {
+			let tribes = T::MaxTribes::get();
+			<SelectedJurors<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<Rankings<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<ReserveCursor<T>>::remove_prefix(jury_call_id, Some(tribes));
+			<JurorSummons<T>>::remove_prefix(jury_call_id, Some(max_candidates::<T>()));
+			<DrawRecords<T>>::remove(jury_call_id);
+			<DrawVoids<T>>::mutate(jury_call_id, |voids| *voids = voids.saturating_add(1));
+			for (challenger, challenge) in <Challenges<T>>::drain_prefix(jury_call_id) {
+				T::Currency::unreserve(&challenger, challenge.bond);
+				Self::deposit_event(Event::ChallengeDropped(jury_call_id, challenger));
+			}
+			<ChallengedReasons<T>>::remove_prefix(jury_call_id, Some(max_challenges::<T>()));
+			<ChallengeCount<T>>::remove(jury_call_id);
+			Self::deposit_event(Event::DrawVoided(jury_call_id));
+		}
+
+		/// Ranks the candidates of every tribe of a Jury Call, the first ones being the jurors.
+		/// Gives the dice as well in an Athenian draw, where the tribes are ranked together.
+		/// Returns None when the algorithm is not supported or the quotas of a stratified draw cannot be met.
+		fn rank_tribes(
+			jury_call_id: JuryCallID,
+			jury_call: &JuryCall<T>,
+			seed: &T::Hash,
+			candidates: &[Vec<AccountOf<T>>],
+			quotas: &[Quota],
+		) -> Option<(Vec<Vec<AccountOf<T>>>, Vec<bool>)> {
+			if jury_call.draw_mode == DrawMode::Athenian {
+				// Every tribe has the same number of seats
+				let seats = jury_call.tribes.first().map_or(0, |tribe| tribe.seats);
+				return sortition::athenian::<T::Hashing, _>(jury_call.algorithm, seed, candidates, seats as usize);
+			}
+			let rankings = jury_call.tribes
+				.iter()
+				.zip(candidates)
+				.enumerate()
+				.map(|(index, (tribe, candidates))| {
+					let tribe_index = index as TribeIndex;
+					let seed = tribe_seed::<T>(seed, tribe_index);
+					Self::rank_candidates(jury_call_id, tribe_index, jury_call.draw_mode, jury_call.algorithm, tribe.seats, &seed, candidates, quotas)
+				})
+				.collect::<Option<Vec<_>>>()?;
+			Some((rankings, Vec::new()))
+		}
+
+		/// Ranks the candidates of a tribe according to the draw mode and algorithm, the first `seats` ones being the jurors.
+		/// Returns None when the algorithm is not supported or the quotas of a stratified draw cannot be met.
+		fn rank_candidates(
+			jury_call_id: JuryCallID,
+			tribe_index: TribeIndex,
+			draw_mode: DrawMode,
+			algorithm: AlgorithmVersion,
+			seats: Selections,
+			seed: &T::Hash,
+			candidates: &[AccountOf<T>],
+			quotas: &[Quota],
+		) -> Option<Vec<AccountOf<T>>> {
+			match draw_mode {
+				DrawMode::Uniform => sortition::shuffle::<T::Hashing, _>(algorithm, seed, candidates),
+				DrawMode::Weighted => {
+					let candidates: Vec<(AccountOf<T>, CandidateWeight)> = candidates
+						.iter()
+						.map(|who| (who.clone(), Self::candidate_weight((jury_call_id, tribe_index), who).unwrap_or_default()))
+						.collect();
+					sortition::weighted_shuffle::<T::Hashing, _>(algorithm, seed, &candidates)
+				},
+				DrawMode::Stratified => {
+					let candidates: Vec<(AccountOf<T>, Vec<(AttributeKey, AttributeValue)>)> = candidates
+						.iter()
+						.map(|who| (who.clone(), Self::candidate_attributes(jury_call_id, who).unwrap_or_default().into_inner()))
+						.collect();
+					sortition::stratified_shuffle::<T::Hashing, _>(algorithm, seed, &candidates, quotas, seats as usize)
+				},
+				// The tribes of an Athenian draw are ranked together, see rank_tribes
+				DrawMode::Athenian => None,
+			}
+		}
+	}
+
+	/// Worst-case weight of draw_jurors: every tribe is full, every candidate is bonded, committed and imported
+	/// from a first stage, and a stratified draw checks every quota and attribute of every candidate for every seat.
+	pub fn draw_jurors_weight<T: Config>() -> Weight {
+		draw_weight::<T>(
+			T::MaxTribes::get() as Weight,
+			max_candidates::<T>() as Weight,
+			T::MaxCandidatesPerTribe::get() as Weight,
+			true,
+		)
+	}
+
+	/// Weight of a draw among `candidates` candidates spread over `tribes` tribes, the largest holding `per_tribe`
+	fn draw_weight<T: Config>(tribes: Weight, candidates: Weight, per_tribe: Weight, stratified: bool) -> Weight {
+		// A candidate is read with their summons in the first stage, weight, attributes, identity, commitment, reveal
+		// and bond, then imported, released or summoned, which writes their candidacy, bond, account and summons
+		let db = T::DbWeight::get().reads_writes(
+			candidates.saturating_mul(8).saturating_add(tribes).saturating_add(4),
+			candidates.saturating_mul(5).saturating_add(tribes.saturating_mul(4)).saturating_add(2),
+		);
+
+		// A stratified draw tries a few random orders, the other modes hash every candidate once
+		if !stratified {
+			return db.saturating_add(candidates.saturating_mul(DRAW_HASH_WEIGHT))
+		}
+		let attempts = sortition::v1::STRATIFIED_ATTEMPTS as Weight;
+		let quotas = (T::MaxQuotas::get() as Weight).saturating_add(1);
+		let hashes = candidates.saturating_mul(attempts.saturating_add(1));
+		let comparisons = candidates
+			.saturating_mul(per_tribe)
+			.saturating_mul(attempts)
+			.saturating_mul(quotas.saturating_mul(quotas))
+			.saturating_mul(T::MaxAttributes::get() as Weight);
+
+		db.saturating_add(hashes.saturating_mul(DRAW_HASH_WEIGHT))
+			.saturating_add(comparisons.saturating_mul(DRAW_COMPARISON_WEIGHT))
+	}
+
+	/// Maximum number of candidates of a Jury Call, every tribe being full
+	fn max_candidates<T: Config>() -> u32 {
+		T::MaxTribes::get().saturating_mul(T::MaxCandidatesPerTribe::get())
+	}
+
+	/// Maximum number of unresolved challenges against a draw, one contesting the seed and one every juror,
+	/// counting generously every candidate as a juror
+	fn max_challenges<T: Config>() -> u32 {
+		max_candidates::<T>().saturating_add(1)
+	}
+
+	/// Worst-case weight of reading and writing `per_candidate` entries for every candidate of a Jury Call,
+	/// `per_tribe` entries for every tribe and `per_challenge` entries for every challenge, as when clearing it.
+	pub fn clear_weight<T: Config>(per_candidate: u64, per_tribe: u64, per_challenge: u64) -> Weight {
+		let entries = (max_candidates::<T>() as Weight).saturating_mul(per_candidate)
+			.saturating_add((T::MaxTribes::get() as Weight).saturating_mul(per_tribe))
+			.saturating_add((max_challenges::<T>() as Weight).saturating_mul(per_challenge));
+		T::DbWeight::get().reads_writes(entries, entries)
+	}
+
+	/// Derives the seed of a tribe from the seed of the draw
+	fn tribe_seed<T: Config>(seed: &T::Hash, tribe_index: TribeIndex) -> T::Hash {
+		T::Hashing::hash_of(&(seed, tribe_index))
+	}
+
+	/// Returns the index of the tribe designated by the selector, if it exists
+	fn find_tribe<T: Config>(jury_call: &JuryCall<T>, selector: &TribeSelector) -> Option<TribeIndex> {
+		match selector {
+			TribeSelector::Index(index) => {
+				if (*index as usize) < jury_call.tribes.len() { Some(*index) } else { None }
+			},
+			TribeSelector::Name(name) => jury_call.tribes
+				.iter()
+				.position(|tribe| tribe.name[..] == name[..])
+				.map(|index| index as TribeIndex),
+		}
+	}
+
 	/// Returns true if the tribes vector contains duplicates
     fn check_duplicate_tribes( tribes: &Vec<Vec<u8>> ) -> bool {
 		let mut tribes_distinct = Vec::<Vec<u8>>::new();
@@ -0,0 +1,286 @@
+//! Storage migrations for pallet-kleroterion
+use crate::pallet::{Config, Pallet};
+use frame_support::{
+	traits::{Get, GetStorageVersion, StorageVersion},
+	weights::Weight,
+};
+
+/// Runs every migration needed to bring the on-chain storage to the current storage version
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if on_chain_version < StorageVersion::new(1) {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	if on_chain_version < StorageVersion::new(2) {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+
+	weight
+}
+
+/// Checks the storage can be migrated from its on-chain version
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+	if on_chain_version < StorageVersion::new(1) {
+		v1::pre_migrate::<T>()
+	} else if on_chain_version < StorageVersion::new(2) {
+		v2::pre_migrate::<T>()
+	} else {
+		Ok(())
+	}
+}
+
+/// Checks the storage is at the current version
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+	v2::post_migrate::<T>()
+}
+
+/// Version 1: the layout introduced after the first deployment.
+/// Tribes are bounded and their candidates live in their own maps,
+/// start_after is split into a registration window and a draw time,
+/// and every Jury Call records its status.
+pub mod v1 {
+	use super::*;
+	use crate::pallet::{CandidateCount, Candidacies, JuryCallStatus, JuryCalls, TribeCandidates};
+	use crate::types::{Candidates, JuryCallID, TribeIndex};
+	use codec::{Decode, Encode};
+	use frame_support::{sp_runtime::print, storage::unhashed};
+	use sp_std::vec::Vec;
+
+	type AccountOf<T> = <T as frame_system::Config>::AccountId;
+
+	/// Tribe as stored by version 0
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct OldTribe<T: Config> {
+		pub name: Vec<u8>,
+		pub candidate_count: Candidates,
+		pub candidates: Vec<AccountOf<T>>,
+	}
+
+	/// Jury Call as stored by version 0
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct OldJuryCall<T: Config> {
+		pub tribes: Vec<OldTribe<T>>,
+		pub selections: u8,
+		pub start_after: u64,
+		pub owner: AccountOf<T>,
+	}
+
+	/// Tribe as stored by version 1, whose name was bounded by MaxTribeNameLen
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct Tribe {
+		pub name: Vec<u8>,
+	}
+
+	/// Jury Call as stored by version 1, whose tribes were bounded by MaxTribes
+	#[derive(Clone, Encode, Decode, PartialEq)]
+	pub struct JuryCall<T: Config> {
+		pub tribes: Vec<Tribe>,
+		pub selections: u8,
+		pub registration_opens: u64,
+		pub registration_closes: u64,
+		pub draw_after: u64,
+		pub owner: AccountOf<T>,
+		pub status: JuryCallStatus,
+	}
+
+	/// Converts every Jury Call to the version 1 layout.
+	/// start_after becomes both the end of the registration and the earliest draw time.
+	/// Jury Calls that do not fit the configured bounds are dropped.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads_writes: u64 = 0;
+
+		// The values change type, so they are rewritten in place
+		let jury_call_ids: Vec<JuryCallID> = <JuryCalls<T>>::iter_keys().collect();
+		for jury_call_id in jury_call_ids {
+			reads_writes = reads_writes.saturating_add(1);
+			let key = <JuryCalls<T>>::hashed_key_for(jury_call_id);
+			match unhashed::get::<OldJuryCall<T>>(&key).and_then(|old| upgrade::<T>(jury_call_id, old, &mut reads_writes)) {
+				Some(jury_call) => unhashed::put(&key, &jury_call),
+				None => unhashed::kill(&key),
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+
+	/// Converts a Jury Call to the version 1 layout and moves its candidates to their own maps
+	fn upgrade<T: Config>(jury_call_id: JuryCallID, old: OldJuryCall<T>, reads_writes: &mut u64) -> Option<JuryCall<T>> {
+		if old.tribes.iter().any(|tribe| tribe.name.len() > T::MaxTribeNameLen::get() as usize) {
+			print("Kleroterion migration: tribe name too long, Jury Call dropped");
+			return None
+		}
+		if old.tribes.len() > T::MaxTribes::get() as usize {
+			print("Kleroterion migration: too many tribes, Jury Call dropped");
+			return None
+		}
+		if old.tribes.iter().any(|tribe| tribe.candidates.len() > T::MaxCandidatesPerTribe::get() as usize) {
+			print("Kleroterion migration: too many candidates in a tribe, Jury Call dropped");
+			return None
+		}
+
+		// Candidates move to their own maps, where version 1 stored no weight
+		for (index, old_tribe) in old.tribes.iter().enumerate() {
+			let tribe_index = index as TribeIndex;
+			let candidates = old_tribe.candidates
+				.iter()
+				.filter(|who| !<Candidacies<T>>::contains_key(jury_call_id, *who));
+			let mut candidate_count: Candidates = 0;
+			for who in candidates {
+				unhashed::put(&<TribeCandidates<T>>::hashed_key_for((jury_call_id, tribe_index), who), &());
+				<Candidacies<T>>::insert(jury_call_id, who, tribe_index);
+				candidate_count = candidate_count.saturating_add(1);
+				*reads_writes = reads_writes.saturating_add(2);
+			}
+			<CandidateCount<T>>::insert(jury_call_id, tribe_index, candidate_count);
+			*reads_writes = reads_writes.saturating_add(1);
+		}
+
+		Some(JuryCall::<T> {
+			tribes: old.tribes.into_iter().map(|tribe| Tribe { name: tribe.name }).collect(),
+			selections: old.selections,
+			registration_opens: 0,
+			registration_closes: old.start_after,
+			draw_after: old.start_after,
+			owner: old.owner,
+			status: JuryCallStatus::Registering,
+		})
+	}
+
+	/// Checks that the storage is still at version 0 and every Jury Call decodes with the old layout
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() < StorageVersion::new(1),
+			"Kleroterion storage is already at version 1"
+		);
+		for jury_call_id in <JuryCalls<T>>::iter_keys() {
+			let key = <JuryCalls<T>>::hashed_key_for(jury_call_id);
+			unhashed::get::<OldJuryCall<T>>(&key)
+				.ok_or("Kleroterion Jury Call does not decode with the version 0 layout")?;
+		}
+		Ok(())
+	}
+}
+
+/// Version 2: every tribe has its own number of seats and candidates have a weight.
+/// Jury Calls record their draw mode and algorithm, identity requirement, candidate bond,
+/// juror compensation, deposit, commit-reveal phase and first stage.
+pub mod v2 {
+	use super::*;
+	use crate::pallet::{DrawMode, JuryCall, JuryCalls, Tribe, TribeCandidates};
+	use crate::types::{JuryCallID, Selections};
+	use frame_support::{
+		sp_runtime::{print, traits::Zero},
+		storage::StoragePrefixedMap,
+		BoundedVec,
+	};
+	use sp_std::{convert::TryInto, vec::Vec};
+
+	/// Converts every Jury Call to the version 2 layout.
+	/// Every tribe gets the former selections as its number of seats and every candidate weighs 1.
+	/// Jury Calls keep their uniform draw with the first algorithm, without identity requirement, bond,
+	/// compensation, deposit, commit-reveal phase nor first stage.
+	/// Jury Calls that do not fit the configured bounds are dropped.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads_writes: u64 = 0;
+
+		<JuryCalls<T>>::translate::<v1::JuryCall<T>, _>(|_, old| {
+			reads_writes = reads_writes.saturating_add(1);
+
+			let mut tribes = Vec::with_capacity(old.tribes.len());
+			for old_tribe in old.tribes {
+				match old_tribe.name.try_into() {
+					Ok(name) => tribes.push(Tribe::<T> { name, seats: old.selections as Selections }),
+					Err(_) => {
+						print("Kleroterion migration: tribe name too long, Jury Call dropped");
+						return None
+					},
+				}
+			}
+			let tribes: BoundedVec<Tribe<T>, T::MaxTribes> = match tribes.try_into() {
+				Ok(tribes) => tribes,
+				Err(_) => {
+					print("Kleroterion migration: too many tribes, Jury Call dropped");
+					return None
+				},
+			};
+
+			Some(JuryCall::<T> {
+				tribes,
+				registration_opens: old.registration_opens,
+				registration_closes: old.registration_closes,
+				draw_after: old.draw_after,
+				draw_mode: DrawMode::Uniform,
+				algorithm: 1,
+				identity: None,
+				candidate_bond: Zero::zero(),
+				juror_compensation: Zero::zero(),
+				deposit: Zero::zero(),
+				commit_reveal: false,
+				first_stage: None,
+				owner: old.owner,
+				status: old.status,
+			})
+		});
+
+		<TribeCandidates<T>>::translate_values::<(), _>(|()| {
+			reads_writes = reads_writes.saturating_add(1);
+			Some(1)
+		});
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+
+	/// Checks that the storage is at version 1 and every Jury Call decodes with the version 1 layout
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		use frame_support::storage::unhashed;
+
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() == StorageVersion::new(1),
+			"Kleroterion storage is not at version 1"
+		);
+		for jury_call_id in <JuryCalls<T>>::iter_keys() {
+			let key = <JuryCalls<T>>::hashed_key_for(jury_call_id);
+			unhashed::get::<v1::JuryCall<T>>(&key)
+				.ok_or("Kleroterion Jury Call does not decode with the version 1 layout")?;
+		}
+		Ok(())
+	}
+
+	/// Checks that the storage is at version 2 and every Jury Call and candidate decodes with the new layout
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		use crate::pallet::CandidateCount;
+		use crate::types::TribeIndex;
+
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() >= StorageVersion::new(2),
+			"Kleroterion storage was not migrated to version 2"
+		);
+		let jury_call_ids: Vec<JuryCallID> = <JuryCalls<T>>::iter_keys().collect();
+		for jury_call_id in jury_call_ids {
+			let jury_call: JuryCall<T> = <JuryCalls<T>>::get(jury_call_id)
+				.ok_or("Kleroterion Jury Call does not decode with the version 2 layout")?;
+			for index in 0..jury_call.tribes.len() {
+				let tribe_index = index as TribeIndex;
+				let candidates = <TribeCandidates<T>>::iter_prefix((jury_call_id, tribe_index)).count();
+				frame_support::ensure!(
+					candidates == <CandidateCount<T>>::get(jury_call_id, tribe_index) as usize,
+					"Kleroterion candidate count does not match the migrated candidates"
+				);
+			}
+		}
+		Ok(())
+	}
+}
@@ -1,22 +1,27 @@
 use crate as pallet_kleroterion;
-use crate::Error;
+use crate::{CandidateWeight, Error, IdentityProvider, JudgementLevel, JuryCallID, RegistrarIndex, WeightSource};
+use std::{cell::RefCell, collections::BTreeMap};
 
 use frame_support::{
+	ord_parameter_types,
 	parameter_types,
-	traits::{OnFinalize, OnInitialize},
+	PalletId,
+	traits::{OnFinalize, OnInitialize, Randomness},
 };	
 use frame_system as system;
 use pallet_timestamp;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
 };
 
 pub const UX_TS_20300101: u64 = 1893452400;
 pub const INIT_TIMESTAMP: u64 = 1632873600; //29/09/2021
 pub const UX_TS_20100101: u64 = 1262300400;
 pub const BLOCK_TIME: u64 = 1000;
+pub const SUMMONS_PERIOD: u64 = 3600;
+pub const CHALLENGE_PERIOD: u64 = 600;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -33,6 +38,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Kleroterion: pallet_kleroterion::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -40,6 +46,26 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const SummonsPeriod: u64 = SUMMONS_PERIOD;
+	pub const ChallengePeriod: u64 = CHALLENGE_PERIOD;
+	pub const ChallengeBond: u64 = 20;
+	pub const MaxTribes: u32 = 4;
+	pub const MaxTribeNameLen: u32 = 16;
+	pub const MaxCandidatesPerTribe: u32 = 10;
+	pub const MaxReasonLen: u32 = 16;
+	pub const MaxQuotas: u32 = 4;
+	pub const MaxAttributes: u32 = 2;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const JuryCallDepositBase: u64 = 5;
+	pub const TribeDeposit: u64 = 2;
+	pub const ByteDeposit: u64 = 1;
+	pub const KleroterionPalletId: PalletId = PalletId(*b"py/klero");
+	pub const CandidateWeightUnit: u64 = 10;
+}
+
+ord_parameter_types! {
+	/// Account allowed to resolve challenges, besides root.
+	pub const Arbiter: u64 = 3;
 }
 
 impl system::Config for Test {
@@ -60,7 +86,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -68,6 +94,18 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_timestamp::Config for Test {
     type Moment = u64;
     type OnTimestampSet = ();
@@ -75,14 +113,77 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+/// Deterministic randomness: the hash of the subject and the current block number.
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
+}
+
+/// Candidates below account 100 weigh their account id, the others weigh nothing.
+pub struct TestWeightSource;
+impl WeightSource<u64> for TestWeightSource {
+	fn weight_of(_jury_call_id: JuryCallID, who: &u64) -> CandidateWeight {
+		if *who < 100 { *who as CandidateWeight } else { 0 }
+	}
+}
+
+thread_local! {
+	static JUDGEMENTS: RefCell<BTreeMap<u64, JudgementLevel>> = RefCell::new(BTreeMap::new());
+}
+
+/// Sets the judgement of registrar 0 on the identity of an account.
+pub fn set_judgement(who: u64, judgement: Option<JudgementLevel>) {
+	JUDGEMENTS.with(|judgements| match judgement {
+		Some(judgement) => judgements.borrow_mut().insert(who, judgement),
+		None => judgements.borrow_mut().remove(&who),
+	});
+}
+
+/// Only registrar 0 judges identities, see set_judgement.
+pub struct TestIdentityProvider;
+impl IdentityProvider<u64> for TestIdentityProvider {
+	fn judgement(who: &u64, registrar: RegistrarIndex) -> Option<JudgementLevel> {
+		if registrar != 0 { return None; }
+		JUDGEMENTS.with(|judgements| judgements.borrow().get(who).copied())
+	}
+}
+
 impl pallet_kleroterion::Config for Test {
 	type Event = TestEvent;
 	type TimeProvider = pallet_timestamp::Pallet<Test>;
+	type Randomness = TestRandomness;
+	type WeightSource = TestWeightSource;
+	type IdentityProvider = TestIdentityProvider;
+	type Currency = Balances;
+	type JuryCallDepositBase = JuryCallDepositBase;
+	type TribeDeposit = TribeDeposit;
+	type ByteDeposit = ByteDeposit;
+	type PalletId = KleroterionPalletId;
+	type SummonsPeriod = SummonsPeriod;
+	type ChallengePeriod = ChallengePeriod;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeOrigin = frame_system::EnsureSignedBy<Arbiter, u64>;
+	type MaxTribes = MaxTribes;
+	type MaxTribeNameLen = MaxTribeNameLen;
+	type MaxCandidatesPerTribe = MaxCandidatesPerTribe;
+	type MaxReasonLen = MaxReasonLen;
+	type MaxQuotas = MaxQuotas;
+	type MaxAttributes = MaxAttributes;
 }
 
 // Build genesis storage according to the mock runtime.
+/// The owner of the Jury Calls, account 1, can afford their deposits.
+/// No identity has a judgement, whatever an earlier test on the same thread set.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	JUDGEMENTS.with(|judgements| judgements.borrow_mut().clear());
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	storage.into()
 }
 
 /// Run until a particular block.
@@ -100,4 +201,9 @@ pub fn run_to_block(n: u64) {
 		System::set_block_number(System::block_number() + 1);
 		System::on_initialize(System::block_number());
 	}
-}
\ No newline at end of file
+}
+
+/// Set the time returned by the TimeProvider, in seconds.
+pub fn set_time(secs: u64) {
+	Timestamp::set_timestamp(secs * 1000);
+}
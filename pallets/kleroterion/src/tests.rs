@@ -1,17 +1,25 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_ok, assert_err};
+use crate::{mock::*, migrations, BalanceWeight, ChallengeReason, DrawMode, Error, IdentityRequirement, JudgementLevel, JuryCallStatus, JuryCalls, Quota, Selections, SummonsStatus, TribeCandidates, TribeSelector, WeightSource};
+use frame_support::{assert_ok, assert_err, traits::{Currency, OnRuntimeUpgrade, StorageVersion}};
 use frame_system::pallet_prelude::*;
+use sp_core::H256;
+use std::convert::TryFrom;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 
 type Event = crate::Event<Test>;
 
+/// Gives the same number of seats to every tribe
+fn with_seats(tribe_names: Vec<Vec<u8>>, seats: Selections) -> Vec<(Vec<u8>, Selections)> {
+	tribe_names.into_iter().map(|name| (name, seats)).collect()
+}
+
 // Fails Probably due to bad definition of expected_event?
 #[test]
 fn it_check_open_jury_call_event() {
 	new_test_ext().execute_with(|| {
 		// Dispatch the call
 		let tribe_names = vec![b"Tribe1".to_vec()];
-		let _res = Kleroterion::open_jury_call(Origin::signed(1), tribe_names, 0, UX_TS_20300101);
+		let _res = Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 0), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None);
 
 		// construct event that should be emitted in the method call
 		let expected_event = TestEvent::Kleroterion(Event::JuryCallOpened(1, 1));
@@ -31,7 +39,7 @@ fn it_fails_with_no_origin() {
 
 		let tribes = vec![b"Tribe1".to_vec()];
 
-		let res = Kleroterion::open_jury_call(Origin::none(), tribes, 5, UX_TS_20300101);
+		let res = Kleroterion::open_jury_call(Origin::none(), with_seats(tribes, 5), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None);
 		// Dispatch a signed open_jury_call extrinsic.
 		assert_err!(res,TestError::BadOrigin);
 	});
@@ -44,7 +52,7 @@ fn it_works_for_minimum_values() {
 		let tribes = vec![b"Tribe1".to_vec()];
 
 		// Dispatch a signed open_jury_call extrinsic.
-		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes, 1, UX_TS_20300101));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribes, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
 
 		// Read pallet storage JuryCallCnt and assert it is 1.
 		assert_eq!(Kleroterion::jury_call_cnt(), 1);
@@ -58,14 +66,14 @@ fn it_jury_call_id_increases() {
 		let tribes = vec![b"Tribe1".to_vec()];
 
 		// Dispatch a signed open_jury_call extrinsic.
-		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes.clone(), 1, UX_TS_20300101));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribes.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
 		// Read pallet storage JuryCallCnt and assert it is 1.
 		assert_eq!(Kleroterion::jury_call_cnt(), 1);
 
 		let tribes = vec![b"Tribe1".to_vec()];
 
 		// Dispatch a second signed open_jury_call extrinsic.
-		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes, 1, UX_TS_20300101));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribes, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
 		// Read pallet storage JuryCallCnt and assert it is 2.
 		assert_eq!(Kleroterion::jury_call_cnt(), 2);
 	});
@@ -78,20 +86,22 @@ fn it_jury_call_check_storage() {
 		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
 
 		// Dispatch a signed open_jury_call extrinsic.
-		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribe_names.clone(), 1, UX_TS_20300101));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
 
 		// Read pallet storage JuryCalls(1) and assert owner is 1
 		assert_eq!(Kleroterion::jury_call(1).unwrap().owner, ensure_signed(Origin::signed(1)).unwrap());
 	
 		// Read pallet storage JuryCalls(1) and assert tribes contains 2 entries
-		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[0].name, b"Tribe1".to_vec());
-		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[1].name, b"Tribe2".to_vec());
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[0].name.to_vec(), b"Tribe1".to_vec());
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[1].name.to_vec(), b"Tribe2".to_vec());
 
-	    // Read pallet storage JuryCalls(1) and assert selections is 1
-		assert_eq!(Kleroterion::jury_call(1).unwrap().selections, 1);
+	    // Read pallet storage JuryCalls(1) and assert each tribe has 1 seat
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[0].seats, 1);
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[1].seats, 1);
 	
-	    // Read pallet storage JuryCalls(1) and assert start_after is UxTs20300101
-		assert_eq!(Kleroterion::jury_call(1).unwrap().start_after, UX_TS_20300101);
+	    // Read pallet storage JuryCalls(1) and assert registration closes and draw after UxTs20300101
+		assert_eq!(Kleroterion::jury_call(1).unwrap().registration_closes, UX_TS_20300101);
+		assert_eq!(Kleroterion::jury_call(1).unwrap().draw_after, UX_TS_20300101);
 	});
 }
 
@@ -102,7 +112,7 @@ fn it_reject_duplicate_tribes() {
 		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe1".to_vec()];
 
 		// Dispatch a signed open_jury_call extrinsic.
-		assert!(Kleroterion::open_jury_call(Origin::signed(1), tribe_names, 1, UX_TS_20300101).is_err());
+		assert!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None).is_err());
 	});
 }
 
@@ -110,13 +120,13 @@ fn it_reject_duplicate_tribes() {
 // Reading the doc on pallet_timestamp, this seems to be dut to the fact that 
 // in tests, the initial timestamp of the genesis block is not set. So time_now in open_jury_call returns 0
 #[test]
-fn it_reject_start_after_in_the_past() {
+fn it_reject_registration_closes_in_the_past() {
 
 	new_test_ext().execute_with(|| {
 		run_to_block(10);
 		let tribe_names = vec![b"Tribe1".to_vec()];
 		// Dispatch a signed extrinsic.
-		assert_err!(Kleroterion::open_jury_call(Origin::signed(1), tribe_names, 1, UX_TS_20100101),Error::<Test>::StartAfterInThePast);
+		assert_err!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20100101, UX_TS_20100101, DrawMode::Uniform, None, 0, 0, false, None),Error::<Test>::RegistrationClosesInThePast);
 	});
 }
 
@@ -127,7 +137,1382 @@ fn it_reject_zero_selections() {
 
 		let tribe_names = vec![b"Tribe1".to_vec()];
 		// Dispatch a signed extrinsic.
-		assert!(Kleroterion::open_jury_call(Origin::signed(1), tribe_names, 0, UX_TS_20300101).is_err());
+		assert!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 0), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None).is_err());
+	});
+}
+
+
+#[test]
+fn it_registers_candidates() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		// Register by index and by name
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(1)));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(3), 1, TribeSelector::Name(b"Tribe1".to_vec())));
+
+		assert_eq!(Kleroterion::candidate_count(1, 0), 1);
+		assert_eq!(Kleroterion::candidates(1, 0), vec![3]);
+		assert_eq!(Kleroterion::candidate_count(1, 1), 1);
+		assert_eq!(Kleroterion::candidates(1, 1), vec![2]);
+		assert_eq!(Kleroterion::candidacy(1, 2), Some(1));
+
+		let expected_event = TestEvent::Kleroterion(Event::CandidateRegistered(1, 1, 2));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+	});
+}
+
+#[test]
+fn it_reject_registration_to_unknown_jury_call_or_tribe() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 2, TribeSelector::Index(0)), Error::<Test>::JuryCallNotFound);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(1)), Error::<Test>::TribeNotFound);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Name(b"Tribe2".to_vec())), Error::<Test>::TribeNotFound);
+	});
+}
+
+#[test]
+fn it_reject_registration_in_two_tribes() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)), Error::<Test>::AlreadyRegistered);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(1)), Error::<Test>::AlreadyRegistered);
+		assert_eq!(Kleroterion::candidate_count(1, 1), 0);
+	});
+}
+
+#[test]
+fn it_reject_registration_outside_window() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), UX_TS_20100101, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)), Error::<Test>::RegistrationNotOpen);
+
+		set_time(UX_TS_20100101);
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+
+		set_time(UX_TS_20300101);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(3), 1, TribeSelector::Index(0)), Error::<Test>::RegistrationClosed);
+	});
+}
+
+#[test]
+fn it_reject_inconsistent_registration_window() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), UX_TS_20300101, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::InvalidRegistrationWindow
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20100101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::DrawBeforeRegistrationCloses
+		);
+	});
+}
+
+#[test]
+fn it_withdraws_candidacy() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(3), 1, TribeSelector::Index(0)));
+
+		assert_ok!(Kleroterion::withdraw_candidacy(Origin::signed(2), 1));
+
+		assert_eq!(Kleroterion::candidate_count(1, 0), 1);
+		assert_eq!(Kleroterion::candidates(1, 0), vec![3]);
+		assert_eq!(Kleroterion::candidacy(1, 2), None);
+
+		let expected_event = TestEvent::Kleroterion(Event::CandidacyWithdrawn(1, 0, 2));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		// A withdrawn candidate can register again, and cannot withdraw twice
+		assert_err!(Kleroterion::withdraw_candidacy(Origin::signed(2), 1), Error::<Test>::NotACandidate);
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+	});
+}
+
+#[test]
+fn it_reject_withdrawal_after_registration_closes() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+
+		set_time(UX_TS_20300101);
+		assert_err!(Kleroterion::withdraw_candidacy(Origin::signed(2), 1), Error::<Test>::WithdrawalClosed);
+		assert_eq!(Kleroterion::candidate_count(1, 0), 1);
+	});
+}
+
+#[test]
+fn it_draws_distinct_jurors_in_each_tribe() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		for who in 10..15 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(20), 1, TribeSelector::Index(1)));
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		assert_eq!(jurors.len(), 2);
+		assert_ne!(jurors[0], jurors[1]);
+		assert!(jurors.iter().all(|juror| (10..15).contains(juror)));
+
+		// A tribe with fewer candidates than seats gets all of them
+		assert_eq!(Kleroterion::selected_jurors(1, 1).unwrap().to_vec(), vec![20]);
+
+		let expected_event = TestEvent::Kleroterion(Event::JurorsSelected(1, 0, jurors));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+	});
+}
+
+#[test]
+fn it_reject_draw_before_draw_after_or_twice() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::DrawTooEarly);
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 2), Error::<Test>::JuryCallNotFound);
+
+		// Registration is closed but the draw is still too early
+		set_time(UX_TS_20300101);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(3), 1, TribeSelector::Index(0)), Error::<Test>::RegistrationClosed);
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::DrawTooEarly);
+
+		set_time(UX_TS_20300101 + 3600);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::AlreadyDrawn);
+	});
+}
+
+#[test]
+fn it_follows_the_jury_call_lifecycle() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Registering);
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+
+		// Closing is only allowed once drawn
+		assert_err!(Kleroterion::close_jury_call(Origin::signed(1), 1), Error::<Test>::InvalidStatusTransition);
+
+		// Anyone can record the end of the registration, once it is reached
+		assert_err!(Kleroterion::close_registration(Origin::signed(3), 1), Error::<Test>::RegistrationNotOver);
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::close_registration(Origin::signed(3), 1));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::ReadyToDraw);
+		assert_err!(Kleroterion::close_registration(Origin::signed(3), 1), Error::<Test>::InvalidStatusTransition);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(4), 1, TribeSelector::Index(0)), Error::<Test>::RegistrationClosed);
+
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(3), 1));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Drawn);
+		for (previous, current) in [
+			(JuryCallStatus::Registering, JuryCallStatus::ReadyToDraw),
+			(JuryCallStatus::ReadyToDraw, JuryCallStatus::Drawn),
+		] {
+			let expected_event = TestEvent::Kleroterion(Event::StatusChanged(1, previous, current));
+			assert!(System::events().iter().any(|a| a.event == expected_event));
+		}
+
+		assert_err!(Kleroterion::close_jury_call(Origin::signed(2), 1), Error::<Test>::NotOwner);
+		assert_err!(Kleroterion::close_jury_call(Origin::signed(1), 1), Error::<Test>::ChallengePeriodOpen);
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Closed);
+
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::AlreadyDrawn);
+		assert_err!(Kleroterion::close_jury_call(Origin::signed(1), 1), Error::<Test>::InvalidStatusTransition);
+	});
+}
+
+#[test]
+fn it_cancels_jury_call() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)));
+
+		assert_err!(Kleroterion::cancel_jury_call(Origin::signed(2), 1, b"Wrong tribes".to_vec()), Error::<Test>::NotOwner);
+		assert_err!(Kleroterion::cancel_jury_call(Origin::signed(1), 1, vec![b'!'; 17]), Error::<Test>::ReasonTooLong);
+		assert_ok!(Kleroterion::cancel_jury_call(Origin::signed(1), 1, b"Wrong tribes".to_vec()));
+
+		let jury_call = Kleroterion::jury_call(1).unwrap();
+		assert_eq!(jury_call.status, JuryCallStatus::Cancelled);
+		assert_eq!(Kleroterion::candidate_count(1, 0), 0);
+		assert!(Kleroterion::candidates(1, 0).is_empty());
+		assert_eq!(Kleroterion::candidacy(1, 2), None);
+
+		let expected_event = TestEvent::Kleroterion(Event::JuryCallCancelled(1, b"Wrong tribes".to_vec()));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		assert_err!(Kleroterion::register_candidate(Origin::signed(2), 1, TribeSelector::Index(0)), Error::<Test>::RegistrationClosed);
+		assert_err!(Kleroterion::cancel_jury_call(Origin::signed(1), 1, vec![]), Error::<Test>::InvalidStatusTransition);
+
+		// Root can cancel any Jury Call
+		assert_ok!(Kleroterion::cancel_jury_call(Origin::root(), 2, vec![]));
+		assert_eq!(Kleroterion::jury_call(2).unwrap().status, JuryCallStatus::Cancelled);
+	});
+}
+
+#[test]
+fn it_reject_cancellation_after_draw() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		assert_err!(Kleroterion::cancel_jury_call(Origin::signed(1), 1, vec![]), Error::<Test>::InvalidStatusTransition);
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Drawn);
+	});
+}
+
+/// Opens a Jury Call with one tribe of 5 candidates (10..15), 2 selections, and draws it.
+fn drawn_jury_call() {
+	let tribe_names = vec![b"Tribe1".to_vec()];
+	assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+	for who in 10..15 {
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+	}
+	set_time(UX_TS_20300101);
+	assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+}
+
+#[test]
+fn it_summons_drawn_jurors_and_keeps_reserves() {
+	new_test_ext().execute_with(|| {
+		drawn_jury_call();
+
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		let reserves = Kleroterion::reserves(1, 0);
+		assert_eq!(jurors.len(), 2);
+		assert_eq!(reserves.len(), 3);
+		assert!(reserves.iter().all(|reserve| !jurors.contains(reserve)));
+
+		let summons = Kleroterion::summons(1, jurors[0]).unwrap();
+		assert_eq!(summons.status, SummonsStatus::Pending);
+		assert_eq!(summons.deadline, UX_TS_20300101 + SUMMONS_PERIOD);
+		assert!(Kleroterion::summons(1, reserves[0]).is_none());
+
+		assert_ok!(Kleroterion::accept_summons(Origin::signed(jurors[0]), 1));
+		assert_eq!(Kleroterion::summons(1, jurors[0]).unwrap().status, SummonsStatus::Accepted);
+		assert_err!(Kleroterion::accept_summons(Origin::signed(jurors[0]), 1), Error::<Test>::SummonsNotPending);
+		assert_err!(Kleroterion::decline_summons(Origin::signed(jurors[0]), 1), Error::<Test>::SummonsNotPending);
+		assert_err!(Kleroterion::accept_summons(Origin::signed(reserves[0]), 1), Error::<Test>::NotSummoned);
+	});
+}
+
+#[test]
+fn it_replaces_declining_juror_with_next_reserve() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		drawn_jury_call();
+
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		let reserves = Kleroterion::reserves(1, 0);
+
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(jurors[1]), 1));
+		assert_eq!(Kleroterion::summons(1, jurors[1]).unwrap().status, SummonsStatus::Declined);
+
+		// The first reserve takes the seat and is summoned in turn
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().to_vec(), vec![jurors[0], reserves[0]]);
+		assert_eq!(Kleroterion::reserves(1, 0), reserves[1..].to_vec());
+		assert_eq!(Kleroterion::summons(1, reserves[0]).unwrap().status, SummonsStatus::Pending);
+
+		let expected_event = TestEvent::Kleroterion(Event::JurorReplaced(1, 0, jurors[1], reserves[0]));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+	});
+}
+
+#[test]
+fn it_replaces_unresponsive_juror_after_deadline() {
+	new_test_ext().execute_with(|| {
+		drawn_jury_call();
+
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		let reserves = Kleroterion::reserves(1, 0);
+
+		assert_err!(Kleroterion::replace_unresponsive_juror(Origin::signed(2), 1, jurors[0]), Error::<Test>::SummonsNotExpired);
+
+		set_time(UX_TS_20300101 + SUMMONS_PERIOD);
+		assert_err!(Kleroterion::accept_summons(Origin::signed(jurors[0]), 1), Error::<Test>::SummonsExpired);
+		assert_ok!(Kleroterion::replace_unresponsive_juror(Origin::signed(2), 1, jurors[0]));
+
+		assert_eq!(Kleroterion::summons(1, jurors[0]).unwrap().status, SummonsStatus::TimedOut);
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().to_vec(), vec![reserves[0], jurors[1]]);
+		assert_eq!(Kleroterion::summons(1, reserves[0]).unwrap().deadline, UX_TS_20300101 + 2 * SUMMONS_PERIOD);
+	});
+}
+
+#[test]
+fn it_leaves_seat_empty_without_reserves() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(11), 1, TribeSelector::Index(0)));
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(10), 1));
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().to_vec(), vec![11]);
+	});
+}
+
+#[test]
+fn it_ranks_all_candidates() {
+	new_test_ext().execute_with(|| {
+		drawn_jury_call();
+
+		let mut ranking = Kleroterion::ranking(1, 0).unwrap().to_vec();
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		assert_eq!(jurors, ranking[..2].to_vec());
+		assert_eq!(Kleroterion::alternates(1, 0), ranking[2..].to_vec());
+		assert_eq!(Kleroterion::rank_of(1, 0, &ranking[3]), Some(4));
+		assert_eq!(Kleroterion::rank_of(1, 0, &1), None);
+
+		// Replacements do not change the ranking
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(jurors[0]), 1));
+		assert_eq!(Kleroterion::ranking(1, 0).unwrap().to_vec(), ranking);
+		assert_eq!(Kleroterion::alternates(1, 0), ranking[2..].to_vec());
+		assert_eq!(Kleroterion::reserves(1, 0), ranking[3..].to_vec());
+
+		// The ranking is a permutation of the candidates
+		ranking.sort();
+		assert_eq!(ranking, (10..15).collect::<Vec<u64>>());
+	});
+}
+
+#[test]
+fn it_reject_oversize_jury_calls() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = (0..5u8).map(|index| vec![b'T', index]).collect::<Vec<_>>();
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::TooManyTribes
+		);
+
+		let tribe_names = vec![b"Tribe1".to_vec(), [b'T'; 17].to_vec()];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::TribeNameTooLong
+		);
+
+		// The bounds are checked before the names are compared
+		let tribe_names = vec![b"Tribe1".to_vec(); 5];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::TooManyTribes
+		);
+	});
+}
+
+#[test]
+fn it_reject_too_many_candidates() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		for who in 10..20 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		assert_err!(Kleroterion::register_candidate(Origin::signed(20), 1, TribeSelector::Index(0)), Error::<Test>::TooManyCandidates);
+		assert_eq!(Kleroterion::candidate_count(1, 0), 10);
+	});
+}
+
+#[test]
+fn it_migrates_version_0_jury_calls() {
+	new_test_ext().execute_with(|| {
+		// A Jury Call as stored before the storage version was introduced
+		let old_jury_call = migrations::v1::OldJuryCall::<Test> {
+			tribes: vec![
+				migrations::v1::OldTribe::<Test> { name: b"Tribe1".to_vec(), candidate_count: 1, candidates: vec![2] },
+				migrations::v1::OldTribe::<Test> { name: b"Tribe2".to_vec(), candidate_count: 0, candidates: vec![] },
+			],
+			selections: 3,
+			start_after: UX_TS_20300101,
+			owner: 1,
+		};
+		frame_support::storage::unhashed::put(&JuryCalls::<Test>::hashed_key_for(1), &old_jury_call);
+		// A Jury Call with more candidates in a tribe than MaxCandidatesPerTribe is dropped
+		let oversize_jury_call = migrations::v1::OldJuryCall::<Test> {
+			tribes: vec![migrations::v1::OldTribe::<Test> { name: b"Tribe1".to_vec(), candidate_count: 11, candidates: (10..21).collect() }],
+			selections: 1,
+			start_after: UX_TS_20300101,
+			owner: 1,
+		};
+		frame_support::storage::unhashed::put(&JuryCalls::<Test>::hashed_key_for(2), &oversize_jury_call);
+		assert_eq!(StorageVersion::get::<Kleroterion>(), StorageVersion::new(0));
+
+		Kleroterion::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Kleroterion>(), StorageVersion::new(2));
+		let jury_call = Kleroterion::jury_call(1).unwrap();
+		assert_eq!(jury_call.tribes.len(), 2);
+		assert_eq!(jury_call.tribes[1].name.to_vec(), b"Tribe2".to_vec());
+		assert_eq!(jury_call.tribes[0].seats, 3);
+		assert_eq!(jury_call.tribes[1].seats, 3);
+		assert_eq!(jury_call.registration_closes, UX_TS_20300101);
+		assert_eq!(jury_call.draw_after, UX_TS_20300101);
+		assert_eq!(jury_call.owner, 1);
+		assert_eq!(jury_call.status, JuryCallStatus::Registering);
+		assert_eq!(jury_call.draw_mode, DrawMode::Uniform);
+		assert_eq!(jury_call.identity, None);
+		assert_eq!(jury_call.algorithm, 1);
+		assert_eq!(Kleroterion::candidates(1, 0), vec![2]);
+		assert_eq!(Kleroterion::candidate_weight((1, 0), 2), Some(1));
+		assert_eq!(Kleroterion::candidate_count(1, 0), 1);
+		assert_eq!(Kleroterion::candidacy(1, 2), Some(0));
+		assert!(Kleroterion::jury_call(2).is_none());
+		assert_eq!(Kleroterion::candidate_count(2, 0), 0);
+		assert_eq!(Kleroterion::candidacy(2, 10), None);
+
+		// Running the upgrade again does not touch migrated storage
+		Kleroterion::on_runtime_upgrade();
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[0].seats, 3);
+	});
+}
+
+#[test]
+fn it_migrates_version_1_jury_calls() {
+	new_test_ext().execute_with(|| {
+		// A Jury Call as stored at version 1, whose candidates had no weight
+		let old_jury_call = migrations::v1::JuryCall::<Test> {
+			tribes: vec![
+				migrations::v1::Tribe { name: b"Tribe1".to_vec() },
+				migrations::v1::Tribe { name: b"Tribe2".to_vec() },
+			],
+			selections: 2,
+			registration_opens: 0,
+			registration_closes: UX_TS_20300101,
+			draw_after: UX_TS_20300101,
+			owner: 1,
+			status: JuryCallStatus::ReadyToDraw,
+		};
+		frame_support::storage::unhashed::put(&JuryCalls::<Test>::hashed_key_for(1), &old_jury_call);
+		frame_support::storage::unhashed::put(&TribeCandidates::<Test>::hashed_key_for((1, 1), 2), &());
+		StorageVersion::new(1).put::<Kleroterion>();
+
+		Kleroterion::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Kleroterion>(), StorageVersion::new(2));
+		let jury_call = Kleroterion::jury_call(1).unwrap();
+		assert_eq!(jury_call.tribes[0].name.to_vec(), b"Tribe1".to_vec());
+		assert_eq!(jury_call.tribes[0].seats, 2);
+		assert_eq!(jury_call.tribes[1].seats, 2);
+		assert_eq!(jury_call.registration_closes, UX_TS_20300101);
+		assert_eq!(jury_call.draw_after, UX_TS_20300101);
+		assert_eq!(jury_call.status, JuryCallStatus::ReadyToDraw);
+		assert_eq!(jury_call.draw_mode, DrawMode::Uniform);
+		assert_eq!(jury_call.algorithm, 1);
+		assert_eq!(jury_call.deposit, 0);
+		assert!(!jury_call.commit_reveal);
+		assert_eq!(jury_call.first_stage, None);
+		assert_eq!(Kleroterion::candidate_weight((1, 1), 2), Some(1));
+
+		// Running the upgrade again does not touch migrated storage
+		Kleroterion::on_runtime_upgrade();
+		assert_eq!(Kleroterion::jury_call(1).unwrap().tribes[1].seats, 2);
+		assert_eq!(Kleroterion::candidate_weight((1, 1), 2), Some(1));
+	});
+}
+
+#[test]
+fn it_weighs_candidates_by_balance() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&10, 30);
+		Balances::make_free_balance_be(&11, 95);
+		assert_eq!(BalanceWeight::<Balances, CandidateWeightUnit>::weight_of(1, &10), 3);
+		assert_eq!(BalanceWeight::<Balances, CandidateWeightUnit>::weight_of(1, &11), 9);
+		assert_eq!(BalanceWeight::<Balances, CandidateWeightUnit>::weight_of(1, &12), 0);
+
+		// The richer candidate comes first about three times as often
+		let candidates: Vec<(u64, u32)> = [10, 11]
+			.iter()
+			.map(|who| (*who, BalanceWeight::<Balances, CandidateWeightUnit>::weight_of(1, who)))
+			.collect();
+		let mut first = [0u32; 2];
+		for n in 0..1_000u64 {
+			let ranking = kleroterion_sortition::weighted_shuffle::<BlakeTwo256, u64>(1, &BlakeTwo256::hash_of(&n), &candidates).unwrap();
+			first[(ranking[0] - 10) as usize] += 1;
+		}
+		assert!(first[1] > 2 * first[0]);
+	});
+}
+
+#[test]
+fn it_draws_each_tribe_seats() {
+	new_test_ext().execute_with(|| {
+		let tribes = vec![(b"Tribe1".to_vec(), 3), (b"Tribe2".to_vec(), 1)];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes, 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		for who in 10..15 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who + 10), 1, TribeSelector::Index(1)));
+		}
+
+		set_time(UX_TS_20300101);
+		// Only the weight of the actual candidates is charged
+		let info = Kleroterion::draw_jurors(Origin::signed(1), 1).unwrap();
+		assert!(info.actual_weight.unwrap() < crate::draw_jurors_weight::<Test>());
+
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().len(), 3);
+		assert_eq!(Kleroterion::alternates(1, 0).len(), 2);
+		assert_eq!(Kleroterion::selected_jurors(1, 1).unwrap().len(), 1);
+		assert_eq!(Kleroterion::alternates(1, 1).len(), 4);
+	});
+}
+
+#[test]
+fn it_reject_zero_seat_tribe() {
+	new_test_ext().execute_with(|| {
+		let tribes = vec![(b"Tribe1".to_vec(), 3), (b"Tribe2".to_vec(), 0)];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), tribes, 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::ZeroSelections
+		);
+	});
+}
+
+#[test]
+fn it_records_candidate_weight() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Weighted, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(42), 1, TribeSelector::Index(0)));
+		assert_eq!(Kleroterion::candidate_weight((1, 0), 42), Some(42));
+
+		// An account without weight can stand in a uniform draw only
+		assert_err!(Kleroterion::register_candidate(Origin::signed(200), 1, TribeSelector::Index(0)), Error::<Test>::ZeroWeight);
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(200), 2, TribeSelector::Index(0)));
+		assert_eq!(Kleroterion::candidate_weight((2, 0), 200), Some(0));
+	});
+}
+
+#[test]
+fn it_draws_proportionally_to_weight() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		for jury_call_id in 1..=20 {
+			assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Weighted, None, 0, 0, false, None));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(1), jury_call_id, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(99), jury_call_id, TribeSelector::Index(0)));
+		}
+
+		set_time(UX_TS_20300101);
+		let mut heavy_wins = 0;
+		for jury_call_id in 1..=20 {
+			assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), jury_call_id));
+			// Every candidate is ranked exactly once
+			let mut ranking = Kleroterion::ranking(jury_call_id, 0).unwrap().to_vec();
+			ranking.sort();
+			assert_eq!(ranking, vec![1, 99]);
+			if Kleroterion::selected_jurors(jury_call_id, 0).unwrap().to_vec() == vec![99] {
+				heavy_wins += 1;
+			}
+		}
+		// Account 99 weighs 99 times more than account 1
+		assert!(heavy_wins >= 15);
+	});
+}
+
+fn quota(key: u16, value: u16, min: Selections, max: Selections) -> Quota {
+	Quota { key, value, min, max }
+}
+
+#[test]
+fn it_draws_panel_meeting_quotas() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 4), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Stratified, None, 0, 0, false, None));
+		// Attribute 0 takes the values 0 and 1 in turn, only 10, 11 and 12 have the value 1 for attribute 1
+		for who in 10..20u64 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			let attributes = vec![(0, (who % 2) as u16), (1, if who < 13 { 1 } else { 0 })];
+			assert_ok!(Kleroterion::set_attributes(Origin::signed(who), 1, attributes));
+		}
+		let quotas = vec![quota(0, 0, 2, 2), quota(0, 1, 2, 2), quota(1, 1, 1, 1)];
+		assert_ok!(Kleroterion::set_quotas(Origin::signed(1), 1, quotas));
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		assert_eq!(jurors.len(), 4);
+		assert_eq!(jurors.iter().filter(|who| *who % 2 == 0).count(), 2);
+		assert_eq!(jurors.iter().filter(|who| **who < 13).count(), 1);
+		assert_eq!(Kleroterion::alternates(1, 0).len(), 6);
+	});
+}
+
+#[test]
+fn it_fails_draw_with_infeasible_quotas() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 4), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Stratified, None, 0, 0, false, None));
+		for who in 10..15u64 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::set_attributes(Origin::signed(who), 1, vec![(0, (who % 2) as u16)]));
+		}
+		// Only 11 and 13 have the value 1
+		assert_ok!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 1, 3, 4)]));
+
+		set_time(UX_TS_20300101);
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::QuotasInfeasible);
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Registering);
+
+		// Each value can fill at most 2 seats of 4
+		assert_ok!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 0, 0, 2), quota(0, 1, 0, 1)]));
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::QuotasInfeasible);
+
+		assert_ok!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 1, 2, 2)]));
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![]), Error::<Test>::QuotasFrozen);
+	});
+}
+
+#[test]
+fn it_checks_quotas_and_attributes() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 4), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Stratified, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 4), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 2, TribeSelector::Index(0)));
+
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 2, vec![]), Error::<Test>::NotStratified);
+		assert_err!(Kleroterion::set_quotas(Origin::signed(2), 1, vec![]), Error::<Test>::NotOwner);
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 0, 3, 2)]), Error::<Test>::InvalidQuota);
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 0, 1, 2), quota(0, 0, 0, 1)]), Error::<Test>::DuplicateQuota);
+		let quotas = (0..5).map(|value| quota(0, value, 0, 1)).collect();
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 1, quotas), Error::<Test>::TooManyQuotas);
+		assert_err!(Kleroterion::set_quotas(Origin::signed(1), 1, vec![quota(0, 0, 0, 1); 5]), Error::<Test>::TooManyQuotas);
+
+		assert_err!(Kleroterion::set_attributes(Origin::signed(10), 2, vec![(0, 0)]), Error::<Test>::NotStratified);
+		assert_err!(Kleroterion::set_attributes(Origin::signed(11), 1, vec![(0, 0)]), Error::<Test>::NotACandidate);
+		assert_err!(Kleroterion::set_attributes(Origin::signed(10), 1, vec![(0, 0), (0, 1)]), Error::<Test>::DuplicateAttribute);
+		assert_err!(Kleroterion::set_attributes(Origin::signed(10), 1, vec![(0, 0), (1, 0), (2, 0)]), Error::<Test>::TooManyAttributes);
+		assert_err!(Kleroterion::set_attributes(Origin::signed(10), 1, vec![(0, 0); 3]), Error::<Test>::TooManyAttributes);
+		assert_ok!(Kleroterion::set_attributes(Origin::signed(10), 1, vec![(0, 1), (1, 0)]));
+		assert_eq!(Kleroterion::candidate_attributes(1, 10).unwrap().to_vec(), vec![(0, 1), (1, 0)]);
+
+		// Withdrawing forgets the attributes
+		assert_ok!(Kleroterion::withdraw_candidacy(Origin::signed(10), 1));
+		assert!(Kleroterion::candidate_attributes(1, 10).is_none());
+	});
+}
+
+#[test]
+fn it_requires_identity_to_register() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		let identity = Some(IdentityRequirement { registrar: 0, judgement: JudgementLevel::KnownGood });
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, identity, 0, 0, false, None));
+
+		assert_err!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)), Error::<Test>::IdentityNotVerified);
+		set_judgement(10, Some(JudgementLevel::Reasonable));
+		assert_err!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)), Error::<Test>::IdentityNotVerified);
+		set_judgement(10, Some(JudgementLevel::KnownGood));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)));
+	});
+}
+
+#[test]
+fn it_excludes_candidates_who_lost_identity_before_draw() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		let identity = Some(IdentityRequirement { registrar: 0, judgement: JudgementLevel::Reasonable });
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, identity, 0, 0, false, None));
+		for who in 10..13 {
+			set_judgement(who, Some(JudgementLevel::KnownGood));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		set_judgement(11, None);
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		let mut jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		jurors.sort();
+		assert_eq!(jurors, vec![10, 12]);
+		assert_eq!(Kleroterion::rank_of(1, 0, &11), None);
+
+		let expected_event = TestEvent::Kleroterion(Event::CandidateExcluded(1, 0, 11));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+	});
+}
+
+/// Opens a Jury Call with a bond of 10, one tribe of 2 seats and 5 candidates (10..15) holding 100 each.
+fn bonded_jury_call() {
+	Balances::make_free_balance_be(&1, 100);
+	let tribe_names = vec![b"Tribe1".to_vec()];
+	assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 10, 0, false, None));
+	for who in 10..15 {
+		Balances::make_free_balance_be(&who, 100);
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+	}
+}
+
+#[test]
+fn it_reserves_and_releases_candidate_bonds() {
+	new_test_ext().execute_with(|| {
+		bonded_jury_call();
+		assert_eq!(Balances::reserved_balance(10), 10);
+		assert_eq!(Kleroterion::bond(1, 10), Some(10));
+
+		Balances::make_free_balance_be(&20, 5);
+		assert_err!(Kleroterion::register_candidate(Origin::signed(20), 1, TribeSelector::Index(0)), Error::<Test>::InsufficientBond);
+		assert!(Kleroterion::candidacy(1, 20).is_none());
+
+		assert_ok!(Kleroterion::withdraw_candidacy(Origin::signed(14), 1));
+		assert_eq!(Balances::reserved_balance(14), 0);
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		// Jurors and alternates keep their bond, an alternate may still be summoned
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		for who in 10..14 {
+			assert_eq!(Balances::reserved_balance(who), 10);
+		}
+
+		// A juror who declines gets their bond back
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(jurors[0]), 1));
+		assert_eq!(Balances::reserved_balance(jurors[0]), 0);
+
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+		for who in 10..15 {
+			assert_eq!(Balances::reserved_balance(who), 0);
+			assert_eq!(Balances::free_balance(who), 100);
+		}
+	});
+}
+
+#[test]
+fn it_slashes_no_shows() {
+	new_test_ext().execute_with(|| {
+		bonded_jury_call();
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+
+		assert_ok!(Kleroterion::accept_summons(Origin::signed(jurors[1]), 1));
+		// Nobody is slashed while the draw can still be challenged
+		assert_err!(Kleroterion::report_absence(Origin::signed(1), 1, jurors[1]), Error::<Test>::ChallengePeriodOpen);
+
+		// A juror who does not answer loses their bond to the owner
+		set_time(UX_TS_20300101 + SUMMONS_PERIOD);
+		assert_ok!(Kleroterion::replace_unresponsive_juror(Origin::signed(2), 1, jurors[0]));
+		assert_eq!(Balances::reserved_balance(jurors[0]), 0);
+		assert_eq!(Balances::free_balance(jurors[0]), 90);
+		assert_eq!(Balances::free_balance(1), 97);
+
+		// So does the alternate summoned in their place
+		let replacement = Kleroterion::selected_jurors(1, 0).unwrap()[0];
+		assert_eq!(Balances::reserved_balance(replacement), 10);
+		set_time(UX_TS_20300101 + 2 * SUMMONS_PERIOD);
+		assert_ok!(Kleroterion::replace_unresponsive_juror(Origin::signed(2), 1, replacement));
+		assert_eq!(Balances::reserved_balance(replacement), 0);
+		assert_eq!(Balances::free_balance(replacement), 90);
+		assert_eq!(Balances::free_balance(1), 107);
+
+		// So does a juror who accepted but did not attend
+		assert_err!(Kleroterion::report_absence(Origin::signed(2), 1, jurors[1]), Error::<Test>::NotOwner);
+		assert_ok!(Kleroterion::report_absence(Origin::signed(1), 1, jurors[1]));
+		assert_eq!(Kleroterion::summons(1, jurors[1]).unwrap().status, SummonsStatus::Absent);
+		assert_eq!(Balances::free_balance(jurors[1]), 90);
+		assert_eq!(Balances::reserved_balance(jurors[1]), 0);
+		assert_eq!(Balances::free_balance(1), 117);
+		assert_err!(Kleroterion::report_absence(Origin::signed(1), 1, jurors[1]), Error::<Test>::SummonsNotAccepted);
+	});
+}
+
+#[test]
+fn it_pays_jurors_out_of_escrow() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 100);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 30, false, None));
+		// The deposit of 13 is reserved, the compensation of 2 seats is paid into escrow
+		assert_eq!(Balances::reserved_balance(1), 13);
+		assert_eq!(Balances::free_balance(1), 27);
+		assert_eq!(Balances::free_balance(Kleroterion::account_id()), 60);
+		assert_eq!(Kleroterion::escrow(1), Some(60));
+		for who in 10..13 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+
+		assert_ok!(Kleroterion::accept_summons(Origin::signed(jurors[0]), 1));
+		assert_err!(Kleroterion::confirm_service(Origin::signed(2), 1, jurors[0]), Error::<Test>::NotOwner);
+
+		// Nobody is paid until the draw can no longer be challenged
+		assert_err!(Kleroterion::confirm_service(Origin::signed(1), 1, jurors[0]), Error::<Test>::ChallengePeriodOpen);
+		Balances::make_free_balance_be(&30, 100);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed));
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_err!(Kleroterion::confirm_service(Origin::signed(1), 1, jurors[0]), Error::<Test>::ChallengePending);
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 30, false));
+
+		assert_err!(Kleroterion::confirm_service(Origin::signed(1), 1, jurors[1]), Error::<Test>::SummonsNotAccepted);
+		assert_ok!(Kleroterion::confirm_service(Origin::signed(1), 1, jurors[0]));
+		assert_eq!(Kleroterion::summons(1, jurors[0]).unwrap().status, SummonsStatus::Served);
+		assert_eq!(Balances::free_balance(jurors[0]), 30);
+		assert_eq!(Kleroterion::escrow(1), Some(30));
+
+		// The unserved seat is refunded on close, next to the bond of the rejected challenger
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+		assert_eq!(Balances::free_balance(1), 77);
+		assert_eq!(Kleroterion::escrow(1), None);
+
+		let expected_events = vec![
+			TestEvent::Kleroterion(Event::CompensationLocked(1, 60)),
+			TestEvent::Kleroterion(Event::JurorPaid(1, jurors[0], 30)),
+			TestEvent::Kleroterion(Event::CompensationRefunded(1, 1, 30)),
+		];
+		for expected_event in expected_events {
+			assert!(System::events().iter().any(|a| a.event == expected_event));
+		}
+	});
+}
+
+#[test]
+fn it_refunds_escrow_on_cancel() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 25, false, None),
+			Error::<Test>::InsufficientCompensationFunds
+		);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 15, false, None));
+		// The deposit of 21 is reserved, the compensation of 4 seats is paid into escrow
+		assert_eq!(Balances::free_balance(1), 19);
+
+		assert_ok!(Kleroterion::cancel_jury_call(Origin::signed(1), 1, vec![]));
+		assert_eq!(Balances::free_balance(1), 79);
+		assert_eq!(Kleroterion::escrow(1), None);
+	});
+}
+
+#[test]
+fn it_reserves_deposit_until_removal() {
+	new_test_ext().execute_with(|| {
+		// 5 for the Jury Call, 2 per tribe and 1 per byte of tribe name
+		let tribe_names = vec![b"Tribe1".to_vec(), b"T2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().deposit, 17);
+		assert_eq!(Balances::reserved_balance(1), 17);
+
+		Balances::make_free_balance_be(&2, 10);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(2), with_seats(vec![b"Tribe1".to_vec()], 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None),
+			Error::<Test>::InsufficientDeposit
+		);
+
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)));
+		assert_err!(Kleroterion::remove_jury_call(Origin::signed(1), 1), Error::<Test>::JuryCallNotFinished);
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+		assert_err!(Kleroterion::remove_jury_call(Origin::signed(2), 1), Error::<Test>::NotOwner);
+		assert_ok!(Kleroterion::remove_jury_call(Origin::signed(1), 1));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(Kleroterion::jury_call(1).is_none());
+		assert!(Kleroterion::candidacy(1, 10).is_none());
+		assert!(Kleroterion::selected_jurors(1, 0).is_none());
+		assert!(Kleroterion::summons(1, 10).is_none());
+		assert_eq!(Kleroterion::candidate_count(1, 0), 0);
+	});
+}
+
+/// The secret of a candidate and the commitment to it
+fn secret_of(who: u64) -> (H256, H256) {
+	let secret = H256::repeat_byte(who as u8);
+	(secret, BlakeTwo256::hash_of(&(who, secret)))
+}
+
+#[test]
+fn it_seeds_draw_with_revealed_secrets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 10, 0, true, None));
+		for who in 10..14 {
+			Balances::make_free_balance_be(&who, 100);
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		// 13 never commits
+		for who in 10..13 {
+			assert_ok!(Kleroterion::commit_secret(Origin::signed(who), 1, secret_of(who).1));
+		}
+		assert_err!(Kleroterion::commit_secret(Origin::signed(10), 1, secret_of(10).1), Error::<Test>::AlreadyCommitted);
+		assert_err!(Kleroterion::reveal_secret(Origin::signed(10), 1, secret_of(10).0), Error::<Test>::RevealNotOpen);
+
+		// 12 withholds its secret
+		set_time(UX_TS_20300101);
+		assert_err!(Kleroterion::commit_secret(Origin::signed(13), 1, secret_of(13).1), Error::<Test>::RegistrationClosed);
+		assert_err!(Kleroterion::reveal_secret(Origin::signed(10), 1, secret_of(11).0), Error::<Test>::InvalidReveal);
+		assert_err!(Kleroterion::reveal_secret(Origin::signed(13), 1, secret_of(13).0), Error::<Test>::NoCommitment);
+		assert_ok!(Kleroterion::reveal_secret(Origin::signed(10), 1, secret_of(10).0));
+		assert_ok!(Kleroterion::reveal_secret(Origin::signed(11), 1, secret_of(11).0));
+		assert_err!(Kleroterion::reveal_secret(Origin::signed(11), 1, secret_of(11).0), Error::<Test>::AlreadyRevealed);
+
+		set_time(UX_TS_20300101 + 3600);
+		assert_err!(Kleroterion::reveal_secret(Origin::signed(12), 1, secret_of(12).0), Error::<Test>::RevealNotOpen);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		// The reveals are hashed in account order
+		let reveals = vec![(10u64, secret_of(10).0), (11u64, secret_of(11).0)];
+		assert_eq!(Kleroterion::revealed_entropy(1), Some(BlakeTwo256::hash_of(&reveals)));
+
+		// Only the candidates who revealed are drawn
+		let mut ranking = Kleroterion::ranking(1, 0).unwrap().to_vec();
+		ranking.sort();
+		assert_eq!(ranking, vec![10, 11]);
+
+		// Withholding a committed secret costs the bond, not committing only excludes
+		assert_eq!(Balances::free_balance(12), 90);
+		assert_eq!(Balances::free_balance(13), 100);
+		assert_eq!(Balances::reserved_balance(13), 0);
+		let expected_event = TestEvent::Kleroterion(Event::CandidateExcluded(1, 0, 12));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+	});
+}
+
+#[test]
+fn it_needs_a_reveal_to_draw() {
+	new_test_ext().execute_with(|| {
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, true, None),
+			Error::<Test>::NoRevealWindow
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 0, 0, true, None),
+			Error::<Test>::NoRevealBond
+		);
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 10, 0, true, None));
+		Balances::make_free_balance_be(&10, 100);
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 1, TribeSelector::Index(0)));
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(10), 2, TribeSelector::Index(0)));
+		assert_err!(Kleroterion::commit_secret(Origin::signed(10), 2, secret_of(10).1), Error::<Test>::NotCommitReveal);
+		assert_ok!(Kleroterion::commit_secret(Origin::signed(10), 1, secret_of(10).1));
+
+		set_time(UX_TS_20300101 + 3600);
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 1), Error::<Test>::NoReveals);
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Registering);
+	});
+}
+
+#[test]
+fn it_records_and_replays_draws() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let tribes = vec![(b"Tribe1".to_vec(), 2), (b"Tribe2".to_vec(), 1)];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes.clone(), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes, 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Weighted, None, 0, 0, false, None));
+		for who in 10..15 {
+			for jury_call_id in 1..=2 {
+				assert_ok!(Kleroterion::register_candidate(Origin::signed(who), jury_call_id, TribeSelector::Index(0)));
+				assert_ok!(Kleroterion::register_candidate(Origin::signed(who + 10), jury_call_id, TribeSelector::Index(1)));
+			}
+		}
+		assert!(Kleroterion::replay_draw(1).is_none());
+
+		set_time(UX_TS_20300101);
+		for jury_call_id in 1..=2 {
+			assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), jury_call_id));
+			let record = Kleroterion::draw_record(jury_call_id).unwrap();
+			assert_eq!(record.block, 3);
+			assert_eq!(record.algorithm_version, crate::DRAW_ALGORITHM_VERSION);
+
+			let rankings = Kleroterion::replay_draw(jury_call_id).unwrap();
+			assert_eq!(rankings[0], Kleroterion::ranking(jury_call_id, 0).unwrap().to_vec());
+			assert_eq!(rankings[1], Kleroterion::ranking(jury_call_id, 1).unwrap().to_vec());
+			assert!(Kleroterion::verify_draw(jury_call_id));
+		}
+
+		// A ranking changed after the draw no longer matches
+		let mut ranking = Kleroterion::ranking(1, 0).unwrap().to_vec();
+		ranking.swap(0, 4);
+		crate::Rankings::<Test>::insert(1, 0, frame_support::BoundedVec::try_from(ranking).unwrap());
+		assert!(!Kleroterion::verify_draw(1));
+
+		// And neither does a changed list of candidates
+		crate::Rankings::<Test>::insert(1, 1, frame_support::BoundedVec::try_from(vec![20, 21]).unwrap());
+		assert!(Kleroterion::replay_draw(1).is_none());
+	});
+}
+
+#[test]
+fn it_draws_with_the_algorithm_of_the_jury_call() {
+	new_test_ext().execute_with(|| {
+		let tribes = vec![(b"Tribe1".to_vec(), 2)];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes.clone(), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), tribes, 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().algorithm, crate::DRAW_ALGORITHM_VERSION);
+		for who in 10..15 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 2, TribeSelector::Index(0)));
+		}
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		// A Jury Call opened with an algorithm the runtime no longer implements cannot be drawn
+		JuryCalls::<Test>::mutate(2, |jury_call| jury_call.as_mut().unwrap().algorithm = 0);
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 2), Error::<Test>::UnsupportedAlgorithm);
+		assert_eq!(Kleroterion::jury_call(2).unwrap().status, JuryCallStatus::Registering);
+
+		// A draw only replays with the algorithm it was recorded with
+		assert!(Kleroterion::verify_draw(1));
+		JuryCalls::<Test>::mutate(1, |jury_call| jury_call.as_mut().unwrap().algorithm = crate::DRAW_ALGORITHM_VERSION + 1);
+		assert!(Kleroterion::replay_draw(1).is_none());
+		assert!(!Kleroterion::verify_draw(1));
+	});
+}
+
+#[test]
+fn it_resolves_challenges_against_jurors() {
+	new_test_ext().execute_with(|| {
+		bonded_jury_call();
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed), Error::<Test>::NotDrawn);
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		let reserve = Kleroterion::reserves(1, 0)[0];
+
+		Balances::make_free_balance_be(&30, 15);
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed), Error::<Test>::InsufficientChallengeBond);
+		Balances::make_free_balance_be(&30, 100);
+		Balances::make_free_balance_be(&31, 100);
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::IneligibleJuror(40)), Error::<Test>::NotSummoned);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::IneligibleJuror(jurors[0])));
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed), Error::<Test>::AlreadyChallenged);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(31), 1, ChallengeReason::WrongSeed));
+		assert_eq!(Balances::reserved_balance(30), 20);
+		assert_eq!(Kleroterion::challenge_count(1), 2);
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(32), 1, ChallengeReason::WrongSeed), Error::<Test>::ReasonAlreadyChallenged);
+
+		// The challenges must be resolved before closing
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(32), 1, ChallengeReason::WrongSeed), Error::<Test>::ChallengePeriodOver);
+		assert_err!(Kleroterion::close_jury_call(Origin::signed(1), 1), Error::<Test>::ChallengePending);
+
+		// An ineligible juror is replaced by the next reserve and gets their bond back
+		assert_err!(Kleroterion::resolve_challenge(Origin::signed(2), 1, 30, true), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(Kleroterion::resolve_challenge(Origin::signed(3), 1, 30, true));
+		assert_eq!(Balances::reserved_balance(30), 0);
+		assert_eq!(Balances::free_balance(30), 100);
+		assert_eq!(Kleroterion::summons(1, jurors[0]).unwrap().status, SummonsStatus::Voided);
+		assert_eq!(Balances::reserved_balance(jurors[0]), 0);
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().to_vec(), vec![reserve, jurors[1]]);
+		assert_eq!(Kleroterion::summons(1, reserve).unwrap().status, SummonsStatus::Pending);
+		assert_err!(Kleroterion::resolve_challenge(Origin::root(), 1, 30, true), Error::<Test>::ChallengeNotFound);
+
+		// A rejected challenger loses their bond to the owner
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 31, false));
+		assert_eq!(Balances::reserved_balance(31), 0);
+		assert_eq!(Balances::free_balance(31), 80);
+		assert_eq!(Balances::free_balance(1), 107);
+		assert_eq!(Kleroterion::challenge_count(1), 0);
+
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+
+		let expected_events = vec![
+			TestEvent::Kleroterion(Event::ChallengeSubmitted(1, 30)),
+			TestEvent::Kleroterion(Event::JurorVoided(1, 0, jurors[0])),
+			TestEvent::Kleroterion(Event::JurorReplaced(1, 0, jurors[0], reserve)),
+			TestEvent::Kleroterion(Event::ChallengeUpheld(1, 30)),
+			TestEvent::Kleroterion(Event::ChallengeRejected(1, 31, 20)),
+		];
+		for expected_event in expected_events {
+			assert!(System::events().iter().any(|a| a.event == expected_event));
+		}
+	});
+}
+
+#[test]
+fn it_takes_one_challenge_per_reason() {
+	new_test_ext().execute_with(|| {
+		bonded_jury_call();
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		for who in 30..35 {
+			Balances::make_free_balance_be(&who, 100);
+		}
+
+		// Contesting the seed does not keep the jurors from being contested
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed));
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(31), 1, ChallengeReason::WrongSeed), Error::<Test>::ReasonAlreadyChallenged);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(31), 1, ChallengeReason::IneligibleJuror(jurors[0])));
+		assert_err!(Kleroterion::submit_challenge(Origin::signed(32), 1, ChallengeReason::IneligibleJuror(jurors[0])), Error::<Test>::ReasonAlreadyChallenged);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(32), 1, ChallengeReason::IneligibleJuror(jurors[1])));
+		assert_eq!(Kleroterion::challenge_count(1), 3);
+		assert_eq!(Kleroterion::challenger_of(1, ChallengeReason::WrongSeed), Some(30));
+		assert_eq!(Balances::reserved_balance(32), 20);
+
+		// A rejected challenge frees its reason
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 30, false));
+		assert!(Kleroterion::challenger_of(1, ChallengeReason::WrongSeed).is_none());
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(33), 1, ChallengeReason::WrongSeed));
+
+		// Voiding the draw frees every reason
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 33, true));
+		assert_eq!(Kleroterion::challenge_count(1), 0);
+		assert!(Kleroterion::challenger_of(1, ChallengeReason::IneligibleJuror(jurors[0])).is_none());
+		assert!(Kleroterion::challenger_of(1, ChallengeReason::IneligibleJuror(jurors[1])).is_none());
+	});
+}
+
+#[test]
+fn it_redraws_when_a_challenge_voids_the_draw() {
+	new_test_ext().execute_with(|| {
+		bonded_jury_call();
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+
+		let seed = Kleroterion::draw_record(1).unwrap().seed;
+
+		Balances::make_free_balance_be(&30, 100);
+		Balances::make_free_balance_be(&31, 100);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed));
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(31), 1, ChallengeReason::IneligibleJuror(jurors[1])));
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(jurors[0]), 1));
+
+		// The draw is forgotten and the other challengers get their bonds back, the candidates keep theirs
+		set_time(UX_TS_20300101 + 60);
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 30, true));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::ReadyToDraw);
+		assert!(Kleroterion::draw_record(1).is_none());
+		assert!(Kleroterion::selected_jurors(1, 0).is_none());
+		assert!(Kleroterion::summons(1, jurors[1]).is_none());
+		assert!(Kleroterion::challenge(1, 31).is_none());
+		assert_eq!(Kleroterion::draw_voids(1), 1);
+		for who in [jurors[0], 30, 31] {
+			assert_eq!(Balances::reserved_balance(who), 0);
+		}
+		for who in (10..15).filter(|who| *who != jurors[0]) {
+			assert_eq!(Balances::reserved_balance(who), 10);
+		}
+		let expected_event = TestEvent::Kleroterion(Event::ChallengeDropped(1, 31));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		// The lot is drawn again with a new seed, without the juror who declined and got their bond back,
+		// and a new challenge period starts
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		assert_eq!(Kleroterion::jury_call(1).unwrap().status, JuryCallStatus::Drawn);
+		assert_eq!(Kleroterion::draw_record(1).unwrap().drawn_at, UX_TS_20300101 + 60);
+		assert_ne!(Kleroterion::draw_record(1).unwrap().seed, seed);
+		assert_eq!(Kleroterion::ranking(1, 0).unwrap().len(), 4);
+		assert!(!Kleroterion::ranking(1, 0).unwrap().contains(&jurors[0]));
+		let expected_event = TestEvent::Kleroterion(Event::CandidateExcluded(1, 0, jurors[0]));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed));
 	});
 }
 
+#[test]
+fn it_reseeds_a_commit_reveal_draw_after_a_void() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101 + 3600, DrawMode::Uniform, None, 10, 0, true, None));
+		for who in 10..14 {
+			Balances::make_free_balance_be(&who, 100);
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::commit_secret(Origin::signed(who), 1, secret_of(who).1));
+		}
+		set_time(UX_TS_20300101);
+		for who in 10..14 {
+			assert_ok!(Kleroterion::reveal_secret(Origin::signed(who), 1, secret_of(who).0));
+		}
+		set_time(UX_TS_20300101 + 3600);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let seed = Kleroterion::draw_record(1).unwrap().seed;
+
+		Balances::make_free_balance_be(&30, 100);
+		assert_ok!(Kleroterion::submit_challenge(Origin::signed(30), 1, ChallengeReason::WrongSeed));
+		assert_ok!(Kleroterion::resolve_challenge(Origin::root(), 1, 30, true));
+
+		// The same reveals give another seed, and every candidate is still bonded and drawn
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		assert_ne!(Kleroterion::draw_record(1).unwrap().seed, seed);
+		assert_eq!(Kleroterion::ranking(1, 0).unwrap().len(), 4);
+		for who in 10..14 {
+			assert_eq!(Balances::reserved_balance(who), 10);
+		}
+	});
+}
+
+#[test]
+fn it_draws_whole_rows_with_athenian_dice() {
+	new_test_ext().execute_with(|| {
+		let tribes = vec![(b"Tribe1".to_vec(), 2), (b"Tribe2".to_vec(), 1)];
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), tribes, 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Athenian, None, 0, 0, false, None),
+			Error::<Test>::UnequalSeats
+		);
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Athenian, None, 0, 0, false, None));
+		for who in 10..15 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+		}
+		for who in 20..23 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(1)));
+		}
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Athenian, None, 0, 0, false, None));
+		for who in 30..33 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 2, TribeSelector::Index(0)));
+		}
+		assert_ok!(Kleroterion::register_candidate(Origin::signed(40), 2, TribeSelector::Index(1)));
+
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+
+		// Tribe2 fills 3 rows, the last 2 slots of Tribe1 are skipped
+		let dice = Kleroterion::draw_record(1).unwrap().dice.into_inner();
+		assert_eq!(dice.len(), 3);
+		assert_eq!(dice.iter().filter(|die| **die).count(), 2);
+		assert_eq!(Kleroterion::selected_jurors(1, 0).unwrap().len(), 2);
+		assert_eq!(Kleroterion::selected_jurors(1, 1).unwrap().len(), 2);
+		assert_eq!(Kleroterion::ranking(1, 0).unwrap().len(), 5);
+		assert_eq!(Kleroterion::alternates(1, 1).len(), 1);
+		assert!(Kleroterion::verify_draw(1));
+
+		// Other dice do not match the draw
+		let mut record = Kleroterion::draw_record(1).unwrap();
+		record.dice = frame_support::BoundedVec::try_from(dice.iter().map(|die| !die).collect::<Vec<_>>()).unwrap();
+		crate::DrawRecords::<Test>::insert(1, record);
+		assert!(!Kleroterion::verify_draw(1));
+
+		// A single row seats a single juror, the other candidates of Tribe1 are alternates
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 2));
+		assert_eq!(Kleroterion::draw_record(2).unwrap().dice.into_inner(), vec![true]);
+		assert_eq!(Kleroterion::selected_jurors(2, 0).unwrap().len(), 1);
+		assert_eq!(Kleroterion::alternates(2, 0), Kleroterion::ranking(2, 0).unwrap()[1..].to_vec());
+		assert!(Kleroterion::alternates(2, 1).is_empty());
+	});
+}
+
+#[test]
+fn it_draws_a_second_stage_among_accepted_jurors() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let tribe_names = vec![b"Tribe1".to_vec(), b"Tribe2".to_vec()];
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 2), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		for who in 10..15 {
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who), 1, TribeSelector::Index(0)));
+			assert_ok!(Kleroterion::register_candidate(Origin::signed(who + 10), 1, TribeSelector::Index(1)));
+		}
+
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(vec![b"Tribe1".to_vec()], 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, Some(1)),
+			Error::<Test>::StageTribesMismatch
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, Some(9)),
+			Error::<Test>::FirstStageNotFound
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101 + 60, DrawMode::Uniform, None, 0, 0, true, Some(1)),
+			Error::<Test>::InvalidSecondStage
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 10, 0, false, Some(1)),
+			Error::<Test>::InvalidSecondStage
+		);
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(2), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, Some(1)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names, 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, Some(1)));
+		assert_err!(Kleroterion::register_candidate(Origin::signed(30), 2, TribeSelector::Index(0)), Error::<Test>::CandidatePoolFixed);
+
+		// A cancelled Jury Call will never have jurors to draw among
+		assert_ok!(Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, None));
+		assert_ok!(Kleroterion::cancel_jury_call(Origin::signed(1), 3, b"Void".to_vec()));
+		assert_err!(
+			Kleroterion::open_jury_call(Origin::signed(1), with_seats(tribe_names.clone(), 1), 0, UX_TS_20300101, UX_TS_20300101, DrawMode::Uniform, None, 0, 0, false, Some(3)),
+			Error::<Test>::FirstStageCancelled
+		);
+
+		// Both jurors of Tribe1 accept, one juror of Tribe2 declines
+		set_time(UX_TS_20300101);
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 1));
+		let first_jurors = Kleroterion::selected_jurors(1, 0).unwrap().to_vec();
+		let second_jurors = Kleroterion::selected_jurors(1, 1).unwrap().to_vec();
+		for who in &first_jurors {
+			assert_ok!(Kleroterion::accept_summons(Origin::signed(*who), 1));
+		}
+		assert_ok!(Kleroterion::accept_summons(Origin::signed(second_jurors[0]), 1));
+		assert_ok!(Kleroterion::decline_summons(Origin::signed(second_jurors[1]), 1));
+
+		// The second stage waits for the first one to be closed
+		assert_err!(Kleroterion::draw_jurors(Origin::signed(1), 2), Error::<Test>::FirstStageNotClosed);
+		set_time(UX_TS_20300101 + CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 1));
+		assert_ok!(Kleroterion::draw_jurors(Origin::signed(1), 2));
+
+		let mut pool = Kleroterion::candidates(2, 0);
+		pool.sort();
+		let mut expected = first_jurors.clone();
+		expected.sort();
+		assert_eq!(pool, expected);
+		assert_eq!(Kleroterion::candidates(2, 1), vec![second_jurors[0]]);
+		assert_eq!(Kleroterion::candidate_count(2, 1), 1);
+		let juror = Kleroterion::selected_jurors(2, 0).unwrap().to_vec();
+		assert_eq!(juror.len(), 1);
+		assert!(first_jurors.contains(&juror[0]));
+		assert_eq!(Kleroterion::selected_jurors(2, 1).unwrap().to_vec(), vec![second_jurors[0]]);
+		assert!(Kleroterion::verify_draw(2));
+
+		let expected_event = TestEvent::Kleroterion(Event::CandidatePoolImported(2, 1));
+		assert!(System::events().iter().any(|a| a.event == expected_event));
+
+		// The first stage is kept until the second stage is over
+		assert_eq!(Kleroterion::second_stage_count(1), 1);
+		assert_err!(Kleroterion::remove_jury_call(Origin::signed(1), 1), Error::<Test>::FirstStageInUse);
+		set_time(UX_TS_20300101 + 2 * CHALLENGE_PERIOD);
+		assert_ok!(Kleroterion::close_jury_call(Origin::signed(1), 2));
+		assert_eq!(Kleroterion::second_stage_count(1), 0);
+		assert_ok!(Kleroterion::remove_jury_call(Origin::signed(1), 1));
+	});
+}
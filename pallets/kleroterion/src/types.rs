@@ -1,4 +1,48 @@
 //Aliases for simple t
 pub type JuryCallID = u32;
-pub type Selections = u8;
 pub type Candidates = u16;
+pub type TribeIndex = u16;
+pub use kleroterion_sortition::{AlgorithmVersion, AttributeKey, AttributeValue, CandidateWeight, Selections};
+pub type RegistrarIndex = u32;
+
+/// Gives the weight of a candidate in a weighted draw, recorded when they register.
+/// The source can be a stake, years of service or a weight assigned by the owner.
+pub trait WeightSource<AccountId> {
+	fn weight_of(jury_call_id: JuryCallID, who: &AccountId) -> CandidateWeight;
+}
+
+/// Every candidate weighs the same
+impl<AccountId> WeightSource<AccountId> for () {
+	fn weight_of(_jury_call_id: JuryCallID, _who: &AccountId) -> CandidateWeight {
+		1
+	}
+}
+
+/// Weighs a candidate by their free balance, one unit of weight for every `Unit` of balance
+pub struct BalanceWeight<Currency, Unit>(sp_std::marker::PhantomData<(Currency, Unit)>);
+
+impl<AccountId, C, Unit> WeightSource<AccountId> for BalanceWeight<C, Unit>
+where
+	C: frame_support::traits::Currency<AccountId>,
+	Unit: frame_support::traits::Get<C::Balance>,
+{
+	fn weight_of(_jury_call_id: JuryCallID, who: &AccountId) -> CandidateWeight {
+		use frame_support::sp_runtime::traits::{SaturatedConversion, Zero};
+		let unit = Unit::get();
+		if unit.is_zero() { return 0; }
+		(C::free_balance(who) / unit).saturated_into()
+	}
+}
+
+/// Gives the judgement a registrar made on the identity of an account, if it is a positive one.
+/// Implemented by the runtime on top of pallet_identity.
+pub trait IdentityProvider<AccountId> {
+	fn judgement(who: &AccountId, registrar: RegistrarIndex) -> Option<crate::JudgementLevel>;
+}
+
+/// No account has a verified identity
+impl<AccountId> IdentityProvider<AccountId> for () {
+	fn judgement(_who: &AccountId, _registrar: RegistrarIndex) -> Option<crate::JudgementLevel> {
+		None
+	}
+}
@@ -31,7 +31,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -100,10 +100,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -263,6 +263,30 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
+parameter_types! {
+	pub const BasicDeposit: Balance = 10_000;
+	pub const FieldDeposit: Balance = 2_500;
+	pub const SubAccountDeposit: Balance = 2_000;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = ();
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -273,10 +297,67 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	/// Drawn jurors have one week to answer their summons.
+	pub const SummonsPeriod: u64 = 7 * 24 * 60 * 60;
+	/// Draws can be challenged for two days.
+	pub const ChallengePeriod: u64 = 2 * 24 * 60 * 60;
+	pub const ChallengeBond: Balance = 10_000;
+	/// The tribes, candidates, quotas and attributes are bounded so that a draw fits in a Normal extrinsic,
+	/// see pallet_kleroterion::draw_jurors_weight.
+	pub const MaxTribes: u32 = 10;
+	pub const MaxTribeNameLen: u32 = 64;
+	pub const MaxCandidatesPerTribe: u32 = 100;
+	pub const MaxReasonLen: u32 = 256;
+	pub const MaxQuotas: u32 = 4;
+	pub const MaxAttributes: u32 = 4;
+	pub const JuryCallDepositBase: Balance = 10_000;
+	pub const TribeDeposit: Balance = 1_000;
+	pub const ByteDeposit: Balance = 100;
+	pub const KleroterionPalletId: PalletId = PalletId(*b"py/klero");
+	/// In a weighted draw, a candidate weighs one for every 10_000 of free balance when they register.
+	pub const CandidateWeightUnit: Balance = 10_000;
+}
+
+/// Gives pallet-kleroterion the judgements recorded by pallet-identity.
+pub struct IdentityJudgements;
+impl pallet_kleroterion::IdentityProvider<AccountId> for IdentityJudgements {
+	fn judgement(
+		who: &AccountId,
+		registrar: pallet_kleroterion::RegistrarIndex,
+	) -> Option<pallet_kleroterion::JudgementLevel> {
+		let registration = Identity::identity(who)?;
+		let (_, judgement) = registration.judgements.iter().find(|(index, _)| *index == registrar)?;
+		match judgement {
+			pallet_identity::Judgement::KnownGood => Some(pallet_kleroterion::JudgementLevel::KnownGood),
+			pallet_identity::Judgement::Reasonable => Some(pallet_kleroterion::JudgementLevel::Reasonable),
+			_ => None,
+		}
+	}
+}
+
 /// Configure the pallet-kleroterion in pallets/kleroterion.
 impl pallet_kleroterion::Config for Runtime {
 	type Event = Event;
 	type TimeProvider = pallet_timestamp::Pallet<Runtime>; // https://stackoverflow.com/questions/68262293/substrate-frame-v2-how-to-use-pallet-timestamp
+	type Randomness = RandomnessCollectiveFlip;
+	type WeightSource = pallet_kleroterion::BalanceWeight<Balances, CandidateWeightUnit>;
+	type IdentityProvider = IdentityJudgements;
+	type Currency = Balances;
+	type JuryCallDepositBase = JuryCallDepositBase;
+	type TribeDeposit = TribeDeposit;
+	type ByteDeposit = ByteDeposit;
+	type PalletId = KleroterionPalletId;
+	type SummonsPeriod = SummonsPeriod;
+	type ChallengePeriod = ChallengePeriod;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTribes = MaxTribes;
+	type MaxTribeNameLen = MaxTribeNameLen;
+	type MaxCandidatesPerTribe = MaxCandidatesPerTribe;
+	type MaxReasonLen = MaxReasonLen;
+	type MaxQuotas = MaxQuotas;
+	type MaxAttributes = MaxAttributes;
 }
 
 
@@ -299,6 +380,7 @@ construct_runtime!(
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 		// Include the custom logic from the pallet-kleroterion in the runtime.
 		Kleroterion: pallet_kleroterion::{Pallet, Call, Storage, Event<T>},
+		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -460,6 +542,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kleroterion_runtime_api::KleroterionApi<Block, AccountId> for Runtime {
+		fn replay_draw(jury_call_id: pallet_kleroterion::JuryCallID) -> Option<Vec<Vec<AccountId>>> {
+			Kleroterion::replay_draw(jury_call_id)
+		}
+
+		fn verify_draw(jury_call_id: pallet_kleroterion::JuryCallID) -> bool {
+			Kleroterion::verify_draw(jury_call_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (